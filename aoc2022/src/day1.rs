@@ -1,8 +1,7 @@
-use std::fs::read_to_string;
+use aoc2023::inputs::read_to_string;
 
-pub fn sum_top_n(file: &str, n: usize) -> i32 {
-    let mut elves = read_to_string(file)
-        .unwrap()
+fn sum_top_n(input: &str, n: usize) -> i32 {
+    let mut elves = input
         .split("\n\n")
         .map(|elf| elf.lines().map(|i| i.parse::<i32>().unwrap()).sum::<i32>())
         .collect::<Vec<_>>();
@@ -11,7 +10,16 @@ pub fn sum_top_n(file: &str, n: usize) -> i32 {
     elves.into_iter().rev().take(n).sum::<i32>()
 }
 
+pub fn part1(input: &str) -> i32 {
+    sum_top_n(input, 1)
+}
+
+pub fn part2(input: &str) -> i32 {
+    sum_top_n(input, 3)
+}
+
 pub fn run(file: &str) {
-    println!("Part 1: {}", sum_top_n(file, 1));
-    println!("Part 2: {}", sum_top_n(file, 3));
+    let input = read_to_string(file).unwrap();
+    println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
 }
@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
-use std::fs::read_to_string;
+
+use aoc2023::inputs::read_to_string;
 
 /// Represents a supported CPU instruction
 enum Instruction {
@@ -58,9 +59,8 @@ impl Cpu {
     }
 }
 
-fn part1(file: &str) {
-    let content = read_to_string(file).unwrap();
-    let commands = content.lines().map(Instruction::from_line);
+pub fn part1(input: &str) -> i32 {
+    let commands = input.lines().map(Instruction::from_line);
 
     let mut cpu = Cpu::new(commands.collect());
     let mut cycles = VecDeque::from_iter((20..221).step_by(40));
@@ -75,30 +75,34 @@ fn part1(file: &str) {
         cpu.run_cycle();
     }
 
-    println!("Part 1: {}", sum)
+    sum
 }
 
-fn part2(file: &str) {
-    let content = read_to_string(file).unwrap();
-    let commands = content.lines().map(Instruction::from_line);
+/// The six rows drawn on the CRT, separated by newlines
+pub fn part2(input: &str) -> String {
+    let commands = input.lines().map(Instruction::from_line);
     let mut cpu = Cpu::new(commands.collect());
-    println!("Part 2:");
+    let mut rows = Vec::new();
     for _row in 0..6 {
+        let mut row = String::new();
         for col in 0..40 {
             if cpu.x <= col + 1 && cpu.x >= col - 1 {
-                print!("#");
+                row.push('#');
             } else {
-                print!(".");
+                row.push('.');
             }
 
             cpu.run_cycle();
         }
-        println!()
+        rows.push(row);
     }
+
+    rows.join("\n")
 }
 
 pub fn run(file: &str) {
-    part1(file);
+    let input = read_to_string(file).unwrap();
+    println!("Part 1: {}", part1(&input));
     println!();
-    part2(file);
+    println!("Part 2:\n{}", part2(&input));
 }
@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
-use std::fs::read_to_string;
+
+use aoc2023::arith;
+use aoc2023::inputs::read_to_string;
 
 enum Operation {
     Add(Option<u128>),
@@ -8,12 +10,12 @@ enum Operation {
 
 impl Operation {
     fn execute(&self, other: u128) -> u128 {
-        // println!("Multiplying: {}", other);
+        let operation = "day11 Operation::execute";
         match self {
-            Operation::Add(Some(i)) => other + i,
-            Operation::Add(None) => other + other,
-            Operation::Multiply(Some(i)) => other * i,
-            Operation::Multiply(None) => other * other,
+            Operation::Add(Some(i)) => arith::add(other, *i, operation),
+            Operation::Add(None) => arith::add(other, other, operation),
+            Operation::Multiply(Some(i)) => arith::mul(other, *i, operation),
+            Operation::Multiply(None) => arith::mul(other, other, operation),
         }
     }
 }
@@ -32,7 +34,7 @@ impl Monkey {
         if let Some(item) = self.items.pop_front() {
             self.throws += 1;
             let item = (self.operation.execute(item) / worry_factor) % common_divisible;
-            if item % self.divisible_test == 0 {
+            if item.is_multiple_of(self.divisible_test) {
                 Some((self.targets.0, item))
             } else {
                 Some((self.targets.1, item))
@@ -102,12 +104,8 @@ fn parse_monkey(content: &str) -> Monkey {
     }
 }
 
-fn parse_monkeys(file: &str) -> Vec<Monkey> {
-    read_to_string(file)
-        .unwrap()
-        .split("\n\n")
-        .map(|m| parse_monkey(m))
-        .collect()
+fn parse_monkeys(input: &str) -> Vec<Monkey> {
+    input.split("\n\n").map(parse_monkey).collect()
 }
 
 fn run_rounds(mut monkeys: Vec<Monkey>, rounds: u128, worry_factor: u128, prod: u128) -> usize {
@@ -129,14 +127,29 @@ fn run_rounds(mut monkeys: Vec<Monkey>, rounds: u128, worry_factor: u128, prod:
     let mut throws = monkeys.iter().map(|m| m.throws).collect::<Vec<_>>();
     throws.sort();
     let num_monkeys = monkeys.len();
-    throws[num_monkeys - 2] * throws[num_monkeys - 1]
+    arith::mul(
+        throws[num_monkeys - 2],
+        throws[num_monkeys - 1],
+        "day11 monkey business",
+    )
 }
 
-pub fn run(file: &str) {
-    let monkeys = parse_monkeys(file);
+fn monkey_business(input: &str, rounds: u128, worry_factor: u128) -> usize {
+    let monkeys = parse_monkeys(input);
     let prod = monkeys.iter().map(|m| m.divisible_test).product();
-    println!("Part 1: {}", run_rounds(monkeys, 20, 3, prod));
+    run_rounds(monkeys, rounds, worry_factor, prod)
+}
 
-    let monkeys = parse_monkeys(file);
-    println!("Part 2: {}", run_rounds(monkeys, 10000, 1, prod))
+pub fn part1(input: &str) -> usize {
+    monkey_business(input, 20, 3)
+}
+
+pub fn part2(input: &str) -> usize {
+    monkey_business(input, 10000, 1)
+}
+
+pub fn run(file: &str) {
+    let input = read_to_string(file).unwrap();
+    println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
 }
@@ -1,5 +1,7 @@
 use std::cmp::{Ord, Ordering};
-use std::fs::read_to_string;
+
+use aoc2023::debug;
+use aoc2023::inputs::read_to_string;
 
 #[derive(Debug, Eq, Clone)]
 enum PacketPart {
@@ -89,28 +91,24 @@ impl PacketPart {
     }
 }
 
-pub fn part1(file: &str) {
-    let packets = read_to_string(file)
-        .unwrap()
+pub fn part1(input: &str) -> usize {
+    let packets = input
         .split("\n\n")
         .map(|s| s.lines().map(PacketPart::from_line).collect::<Vec<_>>())
         .collect::<Vec<_>>();
 
-    println!("{:?}", packets[1]);
-
-    println!(
-        "Part 1: {:?}",
-        packets
-            .iter()
-            .enumerate()
-            .filter(|(_, p)| p[0] <= p[1])
-            .map(|(i, _)| i + 1)
-            .sum::<usize>()
-    );
+    debug!("2022::day13", "Second pair of packets: {:?}", packets[1]);
+
+    packets
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| p[0] <= p[1])
+        .map(|(i, _)| i + 1)
+        .sum()
 }
-pub fn part2(file: &str) {
-    let mut packets = read_to_string(file)
-        .unwrap()
+
+pub fn part2(input: &str) -> usize {
+    let mut packets = input
         .lines()
         .filter(|l| !l.is_empty())
         .map(PacketPart::from_line)
@@ -130,10 +128,11 @@ pub fn part2(file: &str) {
         .position(|p| p == &PacketPart::List(vec![PacketPart::Int(6)]))
         .unwrap();
 
-    println!("Part 2: {:?}", (first + 1) * (second + 1));
+    (first + 1) * (second + 1)
 }
 
 pub fn run(file: &str) {
-    part1(file);
-    part2(file);
+    let input = read_to_string(file).unwrap();
+    println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
 }
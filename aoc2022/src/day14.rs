@@ -1,4 +1,10 @@
-use std::fs::read_to_string;
+use std::fmt;
+
+use aoc2023::animation::Palette;
+use aoc2023::inputs::read_to_string;
+use aoc2023::simulation::{Driver, Simulation};
+use aoc2023::witness::{Check, Witnessed};
+use aoc2023::{Coordinate, Grid, SparseGrid};
 
 use crate::{max, min};
 
@@ -7,89 +13,309 @@ struct Line {
     end: (usize, usize),
 }
 
+/// The rocks in a dense grid that just covers them. Every step of the sand looks them up, which
+/// is a lot cheaper than in a sparse grid.
+#[derive(Clone)]
+struct Rocks {
+    grid: Grid<bool>,
+    /// Where the top left corner of the grid is in the cave
+    origin: Coordinate,
+}
+
+impl Rocks {
+    fn from_lines(lines: &[Line]) -> Rocks {
+        let xs = lines.iter().flat_map(|l| [l.start.0, l.end.0]);
+        let ys = lines.iter().flat_map(|l| [l.start.1, l.end.1]);
+        let (min_x, max_x) = (xs.clone().min().unwrap_or(0), xs.max().unwrap_or(0));
+        let (min_y, max_y) = (ys.clone().min().unwrap_or(0), ys.max().unwrap_or(0));
+
+        let mut grid = Grid::from_size(max_x - min_x + 1, max_y - min_y + 1, false);
+        for line in lines {
+            let (start_x, end_x) = (min(line.start.0, line.end.0), max(line.start.0, line.end.0));
+            let (start_y, end_y) = (min(line.start.1, line.end.1), max(line.start.1, line.end.1));
+            for x in start_x..end_x + 1 {
+                for y in start_y..end_y + 1 {
+                    grid.update((x - min_x) as i64, (y - min_y) as i64, true);
+                }
+            }
+        }
+
+        Rocks {
+            grid,
+            origin: Coordinate::new(min_x as i64, min_y as i64),
+        }
+    }
+
+    fn contains(&self, pos: Coordinate) -> bool {
+        let c = Coordinate::new(pos.x - self.origin.x, pos.y - self.origin.y);
+        self.grid.get_checked(c).copied().unwrap_or(false)
+    }
+
+    /// The leftmost and rightmost columns with room for a rock
+    fn columns(&self) -> (i64, i64) {
+        (self.origin.x, self.origin.x + self.grid.width() as i64 - 1)
+    }
+
+    /// The lowest rock, sand that falls past it keeps falling
+    fn bottom(&self) -> i64 {
+        self.origin.y + self.grid.height() as i64 - 1
+    }
+}
+
 struct Cave {
-    tiles: Vec<Vec<bool>>,
+    rocks: Rocks,
+    /// The sand that has come to rest
+    sand: SparseGrid<bool>,
+    floor: Option<i64>,
+    /// Where the last unit of sand fell through
+    path: Vec<Coordinate>,
 }
 
 impl Cave {
-    fn with_capacity(x: usize, y: usize) -> Cave {
-        // We pad to cover the possible scenarios. In the problem description there's no
-        // "edge". Probably could have used a sparse structure instead.
+    fn add_floor(&mut self) {
+        self.floor = Some(self.rocks.bottom() + 2);
+    }
+
+    fn from_lines(lines: &[Line]) -> Cave {
         Cave {
-            tiles: vec![vec![false; y + 2]; x * 2],
+            rocks: Rocks::from_lines(lines),
+            sand: SparseGrid::new(false),
+            floor: None,
+            path: Vec::new(),
         }
     }
 
-    fn add_floor(&mut self) {
-        let bottom = self.tiles[0].len() - 1;
-        for x in 0..self.tiles.len() {
-            self.tiles[x][bottom] = true;
+    fn is_blocked(&self, pos: Coordinate) -> bool {
+        Some(pos.y) == self.floor || self.rocks.contains(pos) || self.sand[pos]
+    }
+
+    fn block(&mut self, pos: Coordinate) {
+        self.sand[pos] = true;
+    }
+
+    /// Drops a unit of sand, returning where it came to rest - or None if it fell into the abyss
+    /// or the source is already blocked.
+    fn simulate_single_unit(&mut self, source: Coordinate) -> Option<Coordinate> {
+        // A unit follows the last one until it reaches a spot that has been filled since, so it
+        // can start falling from the last free spot on the way
+        while let Some(&pos) = self.path.last() {
+            if !self.is_blocked(pos) {
+                break;
+            }
+            self.path.pop();
+        }
+        if self.path.is_empty() {
+            // We are already full
+            if self.is_blocked(source) {
+                return None;
+            }
+            self.path.push(source);
         }
+
+        let rest = self.fall()?;
+        self.block(rest);
+        Some(rest)
     }
 
-    fn from_lines(lines: &[Line]) -> Cave {
-        let max_x = lines.iter().map(|l| max(l.start.0, l.end.0)).max().unwrap();
-        let max_y = lines.iter().map(|l| max(l.start.1, l.end.1)).max().unwrap();
+    /// Where a unit of sand from the end of the path comes to rest, or None if it falls past the
+    /// bottom. Every spot it falls through is added to the path.
+    fn fall(&mut self) -> Option<Coordinate> {
+        let bottom = self.rocks.bottom();
+        let mut pos = *self.path.last().unwrap();
+        'falling: while pos.y <= bottom + 1 {
+            for dx in [0, -1, 1] {
+                let next = Coordinate::new(pos.x + dx, pos.y + 1);
+                if !self.is_blocked(next) {
+                    pos = next;
+                    self.path.push(pos);
+                    continue 'falling;
+                }
+            }
 
-        let mut cave = Cave::with_capacity(max_x + 1, max_y + 1);
-        for line in lines {
-            cave.add_line(line);
+            return Some(pos);
+        }
+
+        None
+    }
+}
+
+/// Sand pouring into the cave, one unit per step.
+struct Sand {
+    cave: Cave,
+    source: Coordinate,
+    units: Vec<Coordinate>,
+    done: bool,
+}
+
+impl Sand {
+    fn new(cave: Cave, source: Coordinate) -> Self {
+        Sand {
+            cave,
+            source,
+            units: Vec::new(),
+            done: false,
         }
+    }
+}
 
-        cave
+impl Simulation for Sand {
+    fn step(&mut self) {
+        match self.cave.simulate_single_unit(self.source) {
+            Some(pos) => self.units.push(pos),
+            None => self.done = true,
+        }
     }
 
-    fn add_line(&mut self, line: &Line) {
-        let start_x = min(line.start.0, line.end.0);
-        let end_x = max(line.start.0, line.end.0);
+    fn is_done(&self) -> bool {
+        self.done
+    }
 
-        let start_y = min(line.start.1, line.end.1);
-        let end_y = max(line.start.1, line.end.1);
+    fn render(&self) -> String {
+        // Only render the part of the cave the sand can reach, the floor stretches on forever.
+        // The region stays the same between frames.
+        let bottom = self.cave.floor.unwrap_or(self.cave.rocks.bottom());
+        let (mut min_x, mut max_x) = self.cave.rocks.columns();
+        if self.cave.floor.is_some() {
+            // With a floor the sand piles up into a pyramid under the source
+            min_x = min(min_x, self.source.x - bottom);
+            max_x = max(max_x, self.source.x + bottom);
+        }
 
-        for x in start_x..end_x + 1 {
-            for y in start_y..end_y + 1 {
-                self.tiles[x][y] = true;
+        let mut out = String::new();
+        for y in 0..=bottom {
+            for x in min_x - 1..=max_x + 1 {
+                let pos = Coordinate::new(x, y);
+                out.push(if pos == self.source {
+                    '+'
+                } else if self.cave.rocks.contains(pos) || Some(y) == self.cave.floor {
+                    '#'
+                } else if self.cave.sand[pos] {
+                    'o'
+                } else {
+                    '.'
+                });
             }
+            out.push('\n');
         }
+
+        out
     }
 
-    fn simulate_single_unit(&mut self, mut pos: (usize, usize)) -> bool {
-        // We are already full
-        if self.tiles[pos.0][pos.1] {
-            return false;
+    fn palette(&self) -> Palette {
+        Palette::default()
+            .with('o', [230, 190, 90])
+            .with('+', [220, 60, 60])
+    }
+}
+
+/// Where every unit of sand came to rest, in the order they were poured
+pub struct SandCount {
+    rocks: Rocks,
+    floor: Option<i64>,
+    source: Coordinate,
+    units: Vec<Coordinate>,
+}
+
+impl SandCount {
+    /// Pour a unit onto the sand, following the rules from the puzzle rather than `Cave`
+    fn replay_unit(&self, sand: &SparseGrid<bool>) -> Option<Coordinate> {
+        let is_blocked =
+            |pos: Coordinate| Some(pos.y) == self.floor || self.rocks.contains(pos) || sand[pos];
+        let mut pos = self.source;
+        if is_blocked(pos) {
+            return None;
         }
 
-        loop {
-            pos.1 = pos.1 + 1;
-            if pos.1 >= self.tiles[0].len() {
-                self.tiles[pos.0][pos.1 - 1] = true;
-                break false;
-            } else if !self.tiles[pos.0][pos.1] {
-                // Just move on
-            } else if pos.0 > 0 && !self.tiles[pos.0 - 1][pos.1] {
-                pos.0 -= 1;
-            } else if pos.0 + 1 < self.tiles.len() && !self.tiles[pos.0 + 1][pos.1] {
-                pos.0 += 1;
-            } else {
-                self.tiles[pos.0][pos.1 - 1] = true;
-                break true;
+        'falling: loop {
+            // Without a floor, nothing stops it once it's below the lowest rock
+            if self.floor.is_none() && pos.y > self.rocks.bottom() {
+                return None;
+            }
+            let (down, left, right) = (
+                Coordinate::new(pos.x, pos.y + 1),
+                Coordinate::new(pos.x - 1, pos.y + 1),
+                Coordinate::new(pos.x + 1, pos.y + 1),
+            );
+            for next in [down, left, right] {
+                if !is_blocked(next) {
+                    pos = next;
+                    continue 'falling;
+                }
             }
+
+            return Some(pos);
         }
     }
+}
 
-    fn simulate_sand_from(&mut self, pos: (usize, usize)) -> usize {
-        let mut count = 0;
-        while self.simulate_single_unit(pos) {
-            count += 1;
+impl fmt::Display for SandCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.units.last() {
+            Some(last) => write!(
+                f,
+                "{} units of sand came to rest, the last one at {},{}",
+                self.units.len(),
+                last.x,
+                last.y
+            ),
+            None => write!(f, "No sand came to rest"),
         }
+    }
+}
 
-        count
+impl Check<usize> for SandCount {
+    fn check(&self, answer: &usize) -> Result<(), String> {
+        let mut sand = SparseGrid::new(false);
+        for (i, unit) in self.units.iter().enumerate() {
+            match self.replay_unit(&sand) {
+                Some(rest) if rest == *unit => sand.insert(rest, true),
+                Some(rest) => {
+                    return Err(format!(
+                        "Unit {} comes to rest at {},{}, not {},{}",
+                        i + 1,
+                        rest.x,
+                        rest.y,
+                        unit.x,
+                        unit.y
+                    ))
+                }
+                None => return Err(format!("Unit {} never comes to rest", i + 1)),
+            }
+        }
+        if let Some(rest) = self.replay_unit(&sand) {
+            return Err(format!(
+                "Another unit comes to rest at {},{}",
+                rest.x, rest.y
+            ));
+        }
+
+        if self.units.len() != *answer {
+            return Err(format!(
+                "{} units came to rest, not {}",
+                self.units.len(),
+                answer
+            ));
+        }
+
+        Ok(())
     }
 }
 
-fn parse_cave(file: &str) -> Cave {
+fn pour(cave: Cave, driver: &Driver, name: &str) -> Witnessed<usize, SandCount> {
+    let mut sand = Sand::new(cave, Coordinate::new(500, 0));
+    driver.run(name, &mut sand);
+    let count = SandCount {
+        rocks: sand.cave.rocks,
+        floor: sand.cave.floor,
+        source: sand.source,
+        units: sand.units,
+    };
+    Witnessed::new(count.units.len(), count)
+}
+
+fn parse_cave(input: &str) -> Cave {
     let mut lines = Vec::new();
-    for line in read_to_string(file).unwrap().lines() {
+    for line in input.lines() {
         let coordinates = line
             .split(" -> ")
             .map(|l| {
@@ -111,19 +337,34 @@ fn parse_cave(file: &str) -> Cave {
     Cave::from_lines(&lines)
 }
 
-fn part1(file: &str) -> usize {
-    let mut cave = parse_cave(file);
-    cave.simulate_sand_from((500, 0))
+pub fn part1(input: &str) -> usize {
+    part1_explained(input, &Driver::headless()).answer
+}
+
+/// The sand is poured through the driver, which renders it if asked to
+pub fn part1_explained(input: &str, driver: &Driver) -> Witnessed<usize, SandCount> {
+    pour(parse_cave(input), driver, "part1")
+}
+
+pub fn part2(input: &str) -> usize {
+    part2_explained(input, &Driver::headless()).answer
 }
 
-fn part2(file: &str) -> usize {
-    let mut cave = parse_cave(file);
+pub fn part2_explained(input: &str, driver: &Driver) -> Witnessed<usize, SandCount> {
+    let mut cave = parse_cave(input);
     cave.add_floor();
-    // debug_cave(&cave);
-    cave.simulate_sand_from((500, 0))
+    pour(cave, driver, "part2")
 }
 
 pub fn run(file: &str) {
-    println!("Part 1: {}", part1(file));
-    println!("Part 2: {}", part2(file));
+    let input = read_to_string(file).unwrap();
+    let driver = Driver::from_args();
+    for (part, explained) in [
+        ("Part 1", part1_explained(&input, &driver)),
+        ("Part 2", part2_explained(&input, &driver)),
+    ] {
+        println!("{}: {}", part, explained.answer);
+        explained.report(part);
+        explained.check(part);
+    }
 }
@@ -1,8 +1,13 @@
-use std::fs::read_to_string;
+use std::collections::HashSet;
+
+use aoc2023::arith;
+use aoc2023::fuzz::{self, Generator, Rng};
+use aoc2023::inputs::read_to_string;
+use aoc2023::parallel::par_find_map_range;
 
 use crate::{max, min};
 
-#[derive(Eq, PartialEq, Hash, Debug)]
+#[derive(Eq, PartialEq, Hash, Debug, Clone)]
 struct Position {
     x: i64,
     y: i64,
@@ -14,7 +19,8 @@ impl Position {
     }
 
     fn tuning_frequency(&self) -> i64 {
-        (self.x * 4_000_000) + self.y
+        let x = arith::mul(self.x, 4_000_000, "day15 Position::tuning_frequency");
+        arith::add(x, self.y, "day15 Position::tuning_frequency")
     }
 }
 
@@ -48,20 +54,33 @@ impl Segment {
 
 struct Grid {
     sensors: Vec<Sensor>,
+    beacons: HashSet<Position>,
 }
 
 impl Grid {
     fn with_pairs(pairs: Vec<(Position, Position)>) -> Self {
         let mut sensors = Vec::new();
+        let mut beacons = HashSet::new();
         for coord in pairs {
             let distance_to_beacon = coord.0.distance_to(&coord.1);
             sensors.push(Sensor {
                 pos: coord.0,
                 distance_to_beacon,
             });
+            beacons.insert(coord.1);
         }
 
-        Grid { sensors }
+        Grid { sensors, beacons }
+    }
+
+    /// The number of positions on the line where there can't be a beacon
+    fn excluded_on_line(&self, y: i64) -> i64 {
+        let covered = self
+            .beacon_exclusion_segments_on_line(y)
+            .iter()
+            .map(|s| s.end - s.start + 1)
+            .sum::<i64>();
+        covered - self.beacons.iter().filter(|b| b.y == y).count() as i64
     }
 
     fn beacon_exclusion_segments_on_line(&self, y: i64) -> Vec<Segment> {
@@ -78,7 +97,7 @@ impl Grid {
             });
         }
 
-        segments.sort_by(|s1, s2| s1.start.cmp(&s2.start));
+        segments.sort_by_key(|s| s.start);
 
         let mut merged = Vec::<Segment>::new();
         for segment in segments.into_iter() {
@@ -113,42 +132,127 @@ fn parse_line(line: &str) -> (Position, Position) {
     (sensor, beacon)
 }
 
-fn parse_grid(file: &str) -> Grid {
-    let coords: Vec<_> = read_to_string(file)
-        .unwrap()
-        .lines()
-        .map(parse_line)
-        .collect();
-    let grid = Grid::with_pairs(coords);
-    grid
-}
-
-fn part1(file: &str) -> i64 {
-    let grid = parse_grid(file);
-    let line = 2_000_000;
-    let segments = grid.beacon_exclusion_segments_on_line(line);
-    // Not technically correct since there can be multiple segments..
-    // just happens not to be the case.
-    segments.last().unwrap().end - segments.first().unwrap().start
-}
-
-fn part2(file: &str) -> i64 {
-    let grid = parse_grid(file);
-    for i in 0..4_000_000 {
-        let segments = grid.beacon_exclusion_segments_on_line(i);
-        if segments.len() > 1 {
-            return Position {
-                x: segments.first().unwrap().end + 1,
-                y: i,
-            }
-            .tuning_frequency();
+fn parse_grid(content: &str) -> Grid {
+    let coords: Vec<_> = content.lines().map(parse_line).collect();
+    Grid::with_pairs(coords)
+}
+
+pub fn part1(input: &str) -> i64 {
+    let grid = parse_grid(input);
+    grid.excluded_on_line(2_000_000)
+}
+
+pub fn part2(input: &str) -> i64 {
+    let grid = parse_grid(input);
+    par_find_map_range(0..4_000_000, |i| {
+        let y = i as i64;
+        let segments = grid.beacon_exclusion_segments_on_line(y);
+        (segments.len() > 1).then(|| Position {
+            x: segments.first().unwrap().end + 1,
+            y,
+        })
+    })
+    .expect("No solution found")
+    .tuning_frequency()
+}
+
+pub fn run(file: &str) {
+    let input = read_to_string(file).unwrap();
+    println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
+}
+
+/// Checks every position on the line against every sensor
+fn excluded_on_line_brute_force(pairs: &[(Position, Position)], y: i64) -> i64 {
+    let reach = |(sensor, beacon): &(Position, Position)| sensor.distance_to(beacon);
+    let min_x = pairs.iter().map(|p| p.0.x - reach(p)).min().unwrap();
+    let max_x = pairs.iter().map(|p| p.0.x + reach(p)).max().unwrap();
+    (min_x..=max_x)
+        .map(|x| Position { x, y })
+        .filter(|pos| pairs.iter().all(|(_, beacon)| beacon != pos))
+        .filter(|pos| pairs.iter().any(|p| p.0.distance_to(pos) <= reach(p)))
+        .count() as i64
+}
+
+/// Sensor reports, and the line to count the excluded positions on
+#[derive(Clone)]
+struct Report {
+    pairs: Vec<(Position, Position)>,
+    line: i64,
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (sensor, beacon) in self.pairs.iter() {
+            writeln!(
+                f,
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                sensor.x, sensor.y, beacon.x, beacon.y
+            )?;
+        }
+        write!(f, "(line y={})", self.line)
+    }
+}
+
+struct ReportGenerator;
+
+impl Generator for ReportGenerator {
+    type Input = Report;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Report {
+        let size = size as i64;
+        let pairs = (0..rng.between(1, size))
+            .map(|_| {
+                let x = rng.between(-size * 2, size * 2);
+                let y = rng.between(-size * 2, size * 2);
+                let beacon = Position {
+                    x: x + rng.between(-size, size),
+                    y: y + rng.between(-size, size),
+                };
+                (Position { x, y }, beacon)
+            })
+            .collect();
+        Report {
+            pairs,
+            line: rng.between(-size * 2, size * 2),
         }
     }
 
-    panic!("No solution found");
+    fn shrink(&self, report: &Report) -> Vec<Report> {
+        let mut candidates = Vec::new();
+        for i in 0..report.pairs.len() {
+            if report.pairs.len() > 1 {
+                let mut smaller = report.clone();
+                smaller.pairs.remove(i);
+                candidates.push(smaller);
+            }
+
+            // Pull the beacon closer to its sensor
+            let (sensor, beacon) = &report.pairs[i];
+            let closer = Position {
+                x: sensor.x + (beacon.x - sensor.x) / 2,
+                y: sensor.y + (beacon.y - sensor.y) / 2,
+            };
+            if &closer != beacon {
+                let mut smaller = report.clone();
+                smaller.pairs[i].1 = closer;
+                candidates.push(smaller);
+            }
+        }
+
+        candidates
+    }
 }
 
-pub fn run(file: &str) {
-    println!("Part 1: {}", part1(file));
-    println!("Part 2: {}", part2(file));
+/// Cross-check the segment merging against a brute force count on random sensor reports
+pub fn fuzz() {
+    fuzz::run(
+        &ReportGenerator,
+        |report| {
+            let content = report.to_string();
+            let (sensors, _) = content.rsplit_once('\n').unwrap_or_default();
+            parse_grid(sensors).excluded_on_line(report.line)
+        },
+        |report| excluded_on_line_brute_force(&report.pairs, report.line),
+    );
 }
@@ -1,6 +1,10 @@
-use std::collections::HashMap;
-use std::fs::read_to_string;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
 
+use aoc2023::cli;
+use aoc2023::inputs::read_to_string;
+use aoc2023::solvers::Solvers;
+use aoc2023::witness::{Check, Witnessed};
 use regex::Regex;
 
 use crate::max;
@@ -201,14 +205,281 @@ fn dfs2(
     local_max
 }
 
-fn parse_input(file: &str) -> TunnelSystem {
+/// The number of minutes it takes to walk from the valve to every other valve
+fn distances(tunnels: &TunnelSystem, from: NodeHandle) -> Vec<i32> {
+    let mut distances = vec![i32::MAX; tunnels.valves.len()];
+    distances[from] = 0;
+    let mut queue = VecDeque::from([from]);
+    while let Some(current) = queue.pop_front() {
+        for child in tunnels.get(current).children.iter() {
+            if distances[*child] == i32::MAX {
+                distances[*child] = distances[current] + 1;
+                queue.push_back(*child);
+            }
+        }
+    }
+
+    distances
+}
+
+/// The valves worth opening and how far it is to walk between them
+struct UsefulValves {
+    useful: Vec<NodeHandle>,
+    from_start: Vec<i32>,
+    between: Vec<Vec<i32>>,
+}
+
+impl UsefulValves {
+    fn new(tunnels: &TunnelSystem, start: NodeHandle) -> UsefulValves {
+        let useful = (0..tunnels.valves.len())
+            .filter(|handle| tunnels.get(*handle).flow > 0)
+            .collect::<Vec<_>>();
+        let between = useful
+            .iter()
+            .map(|handle| distances(tunnels, *handle))
+            .collect::<Vec<_>>();
+        UsefulValves {
+            useful,
+            from_start: distances(tunnels, start),
+            between,
+        }
+    }
+
+    /// The minutes left after walking to the i:th useful valve and opening it
+    fn left_after_opening(&self, at: Option<usize>, i: usize, remaining: i32) -> i32 {
+        let distance = match at {
+            Some(j) => self.between[j][self.useful[i]],
+            None => self.from_start[self.useful[i]],
+        };
+        remaining - distance - 1
+    }
+}
+
+/// For every set of opened valves (as a bitmask over the useful valves), the most pressure a
+/// single person can release by opening exactly those, and the best subset of it
+fn released_per_set(tunnels: &TunnelSystem, valves: &UsefulValves, time: i32) -> Vec<(i32, usize)> {
+    let mut best = vec![0; 1 << valves.useful.len()];
+    let mut stack: Vec<(Option<usize>, i32, usize, i32)> = vec![(None, time, 0, 0)];
+    while let Some((at, remaining, opened, released)) = stack.pop() {
+        best[opened] = max(best[opened], released);
+        for (i, valve) in valves.useful.iter().enumerate() {
+            let left = valves.left_after_opening(at, i, remaining);
+            if opened & (1 << i) == 0 && left > 0 {
+                let released = released + tunnels.get(*valve).flow * left;
+                stack.push((Some(i), left, opened | (1 << i), released));
+            }
+        }
+    }
+
+    // Let every set know the best of its subsets, the other person doesn't have to open them all
+    let mut best = best
+        .into_iter()
+        .enumerate()
+        .map(|(set, r)| (r, set))
+        .collect::<Vec<_>>();
+    for i in 0..valves.useful.len() {
+        for set in 0..best.len() {
+            if set & (1 << i) != 0 && best[set ^ (1 << i)].0 > best[set].0 {
+                best[set] = best[set ^ (1 << i)];
+            }
+        }
+    }
+
+    best
+}
+
+/// The pair of disjoint sets of valves that together release the most pressure
+fn best_split(best: &[(i32, usize)]) -> (i32, usize, usize) {
+    let all = best.len() - 1;
+    (0..best.len())
+        .map(|set| {
+            (
+                best[set].0 + best[all ^ set].0,
+                best[set].1,
+                best[all ^ set].1,
+            )
+        })
+        .max()
+        .unwrap()
+}
+
+/// A valve opened along the way, with the minutes left once it's open
+struct Opening {
+    valve: String,
+    flow: i32,
+    left: i32,
+}
+
+/// The order each person opens their valves in, each under a label like "You open"
+pub struct ValveOrder {
+    time: i32,
+    orders: Vec<(&'static str, Vec<Opening>)>,
+    /// The flow rate and the tunnels of every valve, to check the order against
+    valves: HashMap<String, (i32, Vec<String>)>,
+}
+
+impl ValveOrder {
+    fn new(tunnels: &TunnelSystem, time: i32, orders: Vec<(&'static str, Vec<Opening>)>) -> Self {
+        let valves = tunnels
+            .valves
+            .iter()
+            .map(|valve| {
+                let children = valve
+                    .children
+                    .iter()
+                    .map(|child| tunnels.get(*child).name.clone())
+                    .collect();
+                (valve.name.clone(), (valve.flow, children))
+            })
+            .collect();
+        ValveOrder {
+            time,
+            orders,
+            valves,
+        }
+    }
+
+    /// The minutes it takes to walk between two valves
+    fn walk(&self, from: &str, to: &str) -> Option<i32> {
+        let mut seen = HashMap::from([(from, 0)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                return Some(seen[current]);
+            }
+            for next in self.valves.get(current)?.1.iter() {
+                if !seen.contains_key(next.as_str()) {
+                    seen.insert(next, seen[current] + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl Check<i32> for ValveOrder {
+    fn check(&self, answer: &i32) -> Result<(), String> {
+        let mut opened = HashSet::new();
+        let mut released = 0;
+        for (who, openings) in self.orders.iter() {
+            let (mut at, mut remaining) = ("AA", self.time);
+            for opening in openings.iter() {
+                let Some((flow, _)) = self.valves.get(&opening.valve) else {
+                    return Err(format!("There's no valve {}", opening.valve));
+                };
+                if !opened.insert(&opening.valve) {
+                    return Err(format!("{} is opened twice", opening.valve));
+                }
+                let walk = self
+                    .walk(at, &opening.valve)
+                    .ok_or_else(|| format!("{} can't be reached", opening.valve))?;
+                if opening.left <= 0 || opening.left > remaining - walk - 1 {
+                    return Err(format!(
+                        "{} {} at minute {}, there isn't time for that",
+                        who,
+                        opening.valve,
+                        self.time - opening.left
+                    ));
+                }
+                released += flow * opening.left;
+                (at, remaining) = (&opening.valve, opening.left);
+            }
+        }
+
+        if released != *answer {
+            return Err(format!("{} pressure is released, not {}", released, answer));
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ValveOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        for (who, openings) in self.orders.iter() {
+            for opening in openings.iter() {
+                lines.push(format!(
+                    "{} {} at minute {}, releasing {} * {} = {}",
+                    who,
+                    opening.valve,
+                    self.time - opening.left,
+                    opening.flow,
+                    opening.left,
+                    opening.flow * opening.left
+                ));
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// The most pressure released by opening the valves of `allowed` (a bitmask over the useful
+/// valves), and the order to open them in
+fn best_order(
+    tunnels: &TunnelSystem,
+    valves: &UsefulValves,
+    at: Option<usize>,
+    remaining: i32,
+    allowed: usize,
+) -> (i32, Vec<Opening>) {
+    let mut best = (0, Vec::new());
+    for i in (0..valves.useful.len()).filter(|i| allowed & (1 << i) != 0) {
+        let left = valves.left_after_opening(at, i, remaining);
+        if left <= 0 {
+            continue;
+        }
+
+        let valve = tunnels.get(valves.useful[i]);
+        let (released, mut rest) = best_order(tunnels, valves, Some(i), left, allowed ^ (1 << i));
+        if released + valve.flow * left > best.0 {
+            rest.insert(
+                0,
+                Opening {
+                    valve: valve.name.clone(),
+                    flow: valve.flow,
+                    left,
+                },
+            );
+            best = (released + valve.flow * left, rest);
+        }
+    }
+
+    best
+}
+
+/// The most pressure one person can release on their own, and the order to open the valves in
+fn single_order(tunnels: &TunnelSystem, start: NodeHandle, time: i32) -> (i32, Vec<Opening>) {
+    let valves = UsefulValves::new(tunnels, start);
+    best_order(tunnels, &valves, None, time, (1 << valves.useful.len()) - 1)
+}
+
+/// Find the most pressure a single person can release for every set of opened valves, only
+/// walking between the valves worth opening. Two people working together open disjoint sets, so
+/// the best pair of disjoint sets is the answer. The total is added up from the order each of the
+/// two opens their valves in, so it's released by exactly the valves they open.
+fn disjoint_orders(
+    tunnels: &TunnelSystem,
+    start: NodeHandle,
+    time: i32,
+) -> (i32, Vec<Opening>, Vec<Opening>) {
+    let valves = UsefulValves::new(tunnels, start);
+    let (_, yours, elephants) = best_split(&released_per_set(tunnels, &valves, time));
+    let (yours_released, yours) = best_order(tunnels, &valves, None, time, yours);
+    let (elephants_released, elephants) = best_order(tunnels, &valves, None, time, elephants);
+    (yours_released + elephants_released, yours, elephants)
+}
+
+fn parse_input(input: &str) -> TunnelSystem {
     let mut tunnels = TunnelSystem::default();
 
     let re = Regex::new(
         r"Valve (..) has flow rate=(-?\d+); tunnels? leads? to valves? ((?:[A-Z]{2},? ?)*)",
     )
     .unwrap();
-    for cap in re.captures_iter(&read_to_string(file).unwrap()) {
+    for cap in re.captures_iter(input) {
         let name = &cap[1];
         let handle = tunnels.get_handle_or_insert(name, Valve::with_name(name.to_string()));
         let children = cap[3]
@@ -224,24 +495,75 @@ fn parse_input(file: &str) -> TunnelSystem {
     tunnels
 }
 
-pub fn run(file: &str) {
-    let tunnels = parse_input(file);
-    let start = tunnels.get_handle("AA");
-    println!(
-        "Part 1: {}",
-        dfs(&tunnels, start, 30, &mut BitSet(0), &mut HashMap::new())
-    );
-    // Did this with finding all possible combination of disjoint set at time 26...
-    // but this v1 solution also did the trick after 4 mins.
-    println!(
-        "Part 2: {}",
+pub fn part1(input: &str) -> i32 {
+    let tunnels = parse_input(input);
+    single_order(&tunnels, tunnels.get_handle("AA"), 30).0
+}
+
+/// Every way of solving part 1, for `--impl`
+fn part1_solvers<'a>(start: NodeHandle) -> Solvers<'a, TunnelSystem, i32> {
+    Solvers::new("Part 1", "orders", move |tunnels| {
+        single_order(tunnels, start, 30).0
+    })
+    .add("dfs", move |tunnels| {
+        dfs(tunnels, start, 30, &mut BitSet(0), &mut HashMap::new())
+    })
+}
+
+pub fn part1_explained(input: &str) -> Witnessed<i32, ValveOrder> {
+    let tunnels = parse_input(input);
+    let (released, order) = single_order(&tunnels, tunnels.get_handle("AA"), 30);
+    let witness = ValveOrder::new(&tunnels, 30, vec![("You open", order)]);
+    Witnessed::new(released, witness)
+}
+
+pub fn part2(input: &str) -> i32 {
+    let tunnels = parse_input(input);
+    disjoint_orders(&tunnels, tunnels.get_handle("AA"), 26).0
+}
+
+/// Every way of solving part 2, for `--impl`
+fn part2_solvers<'a>(start: NodeHandle) -> Solvers<'a, TunnelSystem, i32> {
+    // The first solution searched you and the elephant together, which did the trick after 4
+    // mins. Splitting the valves into disjoint sets is much faster.
+    Solvers::new("Part 2", "disjoint", move |tunnels| {
+        disjoint_orders(tunnels, start, 26).0
+    })
+    .add("dfs", move |tunnels| {
         dfs2(
-            &tunnels,
+            tunnels,
             start,
             start,
             26,
             &mut BitSet(0),
-            &mut HashMap::new()
+            &mut HashMap::new(),
         )
-    )
+    })
+}
+
+pub fn part2_explained(input: &str) -> Witnessed<i32, ValveOrder> {
+    let tunnels = parse_input(input);
+    let (released, yours, elephants) = disjoint_orders(&tunnels, tunnels.get_handle("AA"), 26);
+    let orders = vec![("You open", yours), ("The elephant opens", elephants)];
+    let witness = ValveOrder::new(&tunnels, 26, orders);
+    Witnessed::new(released, witness)
+}
+
+pub fn run(file: &str) {
+    let input = read_to_string(file).unwrap();
+    if cli::args().flag("explain") || cli::args().flag("check") {
+        for (part, explained) in [
+            ("Part 1", part1_explained(&input)),
+            ("Part 2", part2_explained(&input)),
+        ] {
+            println!("{}: {}", part, explained.answer);
+            explained.report(part);
+            explained.check(part);
+        }
+    } else {
+        let tunnels = parse_input(&input);
+        let start = tunnels.get_handle("AA");
+        println!("Part 1: {}", part1_solvers(start).run(&tunnels));
+        println!("Part 2: {}", part2_solvers(start).run(&tunnels));
+    }
 }
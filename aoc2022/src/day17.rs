@@ -1,5 +1,11 @@
 use std::collections::HashMap;
-use std::fs::read_to_string;
+use std::fmt;
+
+use aoc2023::debug;
+use aoc2023::inputs::read_to_string;
+use aoc2023::simulation::{Driver, Simulation};
+use aoc2023::witness::{Check, Witnessed};
+use aoc2023::{Coordinate, SparseGrid};
 
 use crate::max;
 
@@ -132,7 +138,7 @@ type HistoryEntry = (Rock, usize, bool);
 
 #[derive(Default)]
 struct Chamber {
-    space: Vec<Vec<bool>>,
+    space: SparseGrid<bool>,
     tallest: usize,
 }
 
@@ -145,9 +151,7 @@ impl Chamber {
     }
 
     fn is_occupied(&self, x: i64, y: i64) -> bool {
-        let y: usize = y.try_into().unwrap();
-        let x: usize = x.try_into().unwrap();
-        self.space[y][x]
+        self.space[Coordinate::new(x, y)]
     }
 
     fn can_move(&self, pos: &Vec<Position>, x: i64, y: i64) -> bool {
@@ -183,37 +187,36 @@ impl Chamber {
         }
     }
 
-    fn debug_chamber(&self, pos: &[Position]) {
-        println!("Tallest: {}", self.tallest);
-        for y in (0..self.tallest + 7).rev() {
-            for x in 0..self.space[0].len() {
-                if self.space[y][x] {
-                    print!("#");
-                } else if pos
-                    .iter()
-                    .any(|p| p.x == x.try_into().unwrap() && p.y == y.try_into().unwrap())
-                {
-                    print!("@");
+    /// Renders the top of the chamber, the way the puzzle description draws it
+    fn render(&self, rows: usize) -> String {
+        let top = self.tallest + 3;
+        let mut out = String::new();
+        for y in (top.saturating_sub(rows)..top).rev() {
+            out.push('|');
+            for x in 0..7 {
+                out.push(if self.is_occupied(x, y as i64) {
+                    '#'
                 } else {
-                    print!("*");
-                }
+                    '.'
+                });
             }
-            println!();
+            out.push_str("|\n");
+        }
+
+        if top <= rows {
+            out.push_str("+-------+\n");
         }
+
+        out
     }
 
     fn drop_rock(
         &mut self,
         rock: &Rock,
         pushes: &mut Pushes,
-        debug: bool,
         history: &mut Vec<HistoryEntry>,
         heights: &mut Vec<usize>,
     ) {
-        if self.space.len() < self.tallest + 10 {
-            self.space.extend(vec![vec![false; 7]; 20]);
-        }
-
         let mut max_range = 0;
         let mut current = 0;
         for i in 0..7 {
@@ -221,7 +224,7 @@ impl Chamber {
                 continue;
             }
 
-            if !self.space[self.tallest - 1][i] {
+            if !self.is_occupied(i, self.tallest as i64 - 1) {
                 current += 1;
                 max_range = max(current, max_range)
             } else {
@@ -233,14 +236,7 @@ impl Chamber {
 
         let mut rock_positions = rock.start_position(2, (self.tallest + 3).try_into().unwrap());
         let mut should_push = true;
-        let mut buffer = String::new();
-        let stdin = std::io::stdin(); // We get `Stdin` here
         loop {
-            if debug {
-                stdin.read_line(&mut buffer).unwrap();
-                self.debug_chamber(&rock_positions);
-            }
-
             if should_push {
                 let push = pushes.get_next();
                 if self.can_push(&rock_positions, push) {
@@ -252,15 +248,13 @@ impl Chamber {
                 should_push = true;
             } else {
                 for pos in rock_positions.iter() {
-                    let y: usize = pos.y.try_into().unwrap();
-                    let x: usize = pos.x.try_into().unwrap();
-                    self.space[y][x] = true;
+                    self.space.insert(Coordinate::new(pos.x, pos.y), true);
                 }
 
                 // Find the tallest.. will be in the current tallest +4
                 for x in 0..7 {
                     for y in (self.tallest..self.tallest + 4).rev() {
-                        if self.space[y][x] {
+                        if self.is_occupied(x, y as i64) {
                             self.tallest = y + 1;
                             break;
                         }
@@ -273,20 +267,78 @@ impl Chamber {
     }
 }
 
-fn parse_pushes(file: &str) -> Vec<Push> {
-    read_to_string(file)
-        .unwrap()
+fn parse_pushes(input: &str) -> Vec<Push> {
+    input
         .chars()
         .filter(|c| !c.is_whitespace())
         .map(Push::parse)
         .collect()
 }
 
-fn part1(history: &[usize]) {
-    println!("Part 1: {}", history[2022]);
+/// The repeating part of the tower, which is what makes a trillion rocks feasible
+pub struct Cycle {
+    start: usize,
+    length: usize,
+    start_height: usize,
+    height_gain: usize,
+    cycles: usize,
+    leftover: usize,
+    leftover_height: usize,
+    /// The height after every rock that was simulated, to check the cycle against
+    heights: Vec<usize>,
+}
+
+impl Check<usize> for Cycle {
+    fn check(&self, answer: &usize) -> Result<(), String> {
+        // The heights have to repeat for as long as the tower was simulated
+        for rock in self.start..self.heights.len() - self.length {
+            let gain = self.heights[rock + self.length] - self.heights[rock];
+            if gain != self.height_gain {
+                return Err(format!(
+                    "The tower grows {} rows from rock {}, not {}",
+                    gain, rock, self.height_gain
+                ));
+            }
+        }
+
+        let rocks = self.start + self.cycles * self.length + self.leftover;
+        if rocks != 1_000_000_000_000 {
+            return Err(format!("The cycles add up to {} rocks", rocks));
+        }
+        if self.heights[self.start] != self.start_height
+            || self.heights[self.start + self.leftover] - self.start_height != self.leftover_height
+        {
+            return Err("The heights don't match the simulation".into());
+        }
+
+        let height = self.start_height + self.cycles * self.height_gain + self.leftover_height;
+        if height != *answer {
+            return Err(format!(
+                "The cycles add up to {} rows, not {}",
+                height, answer
+            ));
+        }
+
+        Ok(())
+    }
 }
 
-fn part2(history: &[HistoryEntry], heights: &[usize]) {
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "The tower repeats every {} rocks from rock {}, growing {} rows per cycle",
+            self.length, self.start, self.height_gain
+        )?;
+        write!(
+            f,
+            "{} rows before the cycle + {} cycles * {} rows + {} rows for the {} rocks left over",
+            self.start_height, self.cycles, self.height_gain, self.leftover_height, self.leftover
+        )
+    }
+}
+
+fn tower_height(history: &[HistoryEntry], heights: &[usize]) -> Witnessed<usize, Cycle> {
     let mut previous = HashMap::new();
     let mut cycle = (0, 0);
     for (i, entry) in history.iter().enumerate().skip(150) {
@@ -297,9 +349,6 @@ fn part2(history: &[HistoryEntry], heights: &[usize]) {
         if let Some(prev) = previous.get(entry) {
             cycle = (*prev, i);
             break;
-            //println!("Cycle: {} -> {} ({})", cycle.0, cycle.1, cycle.1-cycle.0);
-            //println!("Height: {} -> {} ({})", heights[cycle.0], heights[cycle.1], heights[cycle.1]-heights[cycle.0]);
-            //previous.insert(*entry, i);
         } else {
             previous.insert(*entry, i);
         }
@@ -315,16 +364,21 @@ fn part2(history: &[HistoryEntry], heights: &[usize]) {
     let cycles_left = iters_left / cycle_len;
     let leftover = iters_left % cycle_len;
     let leftover_height = heights[cycle.0 + leftover] - heights[cycle.0];
-    println!("Cycle: {} -> {} ({})", cycle.0, cycle.1, cycle_len);
-    println!(
-        "Height difference: {} -> {} ({})",
-        heights[cycle.0], heights[cycle.1], height_diff
+    let target = "2022::day17";
+    debug!(target, "Cycle: {} -> {} ({})", cycle.0, cycle.1, cycle_len);
+    debug!(
+        target,
+        "Height difference: {} -> {} ({})", heights[cycle.0], heights[cycle.1], height_diff
+    );
+    debug!(target, "Iters left: {}", iters_left);
+    debug!(target, "Cycles left: {}", cycles_left);
+    debug!(
+        target,
+        "Leftover len: {} height: {}", leftover, leftover_height
     );
-    println!("Iters left: {}", iters_left);
-    println!("Cycles left: {}", cycles_left);
-    println!("Leftover len: {} height: {}", leftover, leftover_height);
-    println!("Height at 140: {}", heights[140]);
-    println!(
+    debug!(target, "Height at 140: {}", heights[140]);
+    debug!(
+        target,
         "Cycle test: {} = {}",
         heights[cycle.0 + cycle_len] + height_diff * 2,
         heights[cycle.1 + cycle_len * 2]
@@ -336,26 +390,93 @@ fn part2(history: &[HistoryEntry], heights: &[usize]) {
         + (cycles_left * height_diff)
         + heights[cycle.0 + (iters_left % cycle_len)]
         - heights[cycle.0];
-    println!("Height: {}", height);
+    let cycle = Cycle {
+        start: cycle.0,
+        length: cycle_len,
+        start_height: heights[cycle.0],
+        height_gain: height_diff,
+        cycles: cycles_left,
+        leftover,
+        leftover_height,
+        heights: heights.to_vec(),
+    };
+    Witnessed::new(height, cycle)
 }
 
-pub fn run(file: &str) {
-    let mut pushes = Pushes::new(parse_pushes(file));
-    let mut chamber = Chamber::default();
-    let rocks = vec![
-        Rock::HorizontalLine,
-        Rock::Plus,
-        Rock::ReverseL,
-        Rock::VerticalLine,
-        Rock::Square,
-    ];
-    let mut history = Vec::new();
-    let mut heights = Vec::new();
-    for i in 0..10_000 {
-        let rock = &rocks[i % 5];
-        chamber.drop_rock(rock, &mut pushes, false, &mut history, &mut heights);
+const ROCKS: [Rock; 5] = [
+    Rock::HorizontalLine,
+    Rock::Plus,
+    Rock::ReverseL,
+    Rock::VerticalLine,
+    Rock::Square,
+];
+
+/// Rocks falling into the chamber, one rock per step.
+struct RockFall {
+    chamber: Chamber,
+    pushes: Pushes,
+    history: Vec<HistoryEntry>,
+    heights: Vec<usize>,
+    total: usize,
+}
+
+impl Simulation for RockFall {
+    fn step(&mut self) {
+        let rock = ROCKS[self.history.len() % ROCKS.len()];
+        self.chamber.drop_rock(
+            &rock,
+            &mut self.pushes,
+            &mut self.history,
+            &mut self.heights,
+        );
     }
 
-    part1(&heights);
-    part2(&history, &heights);
+    fn is_done(&self) -> bool {
+        self.history.len() >= self.total
+    }
+
+    fn render(&self) -> String {
+        self.chamber.render(40)
+    }
+}
+
+fn fall_rocks(input: &str, driver: &Driver, name: &str, total: usize) -> RockFall {
+    let mut fall = RockFall {
+        chamber: Chamber::default(),
+        pushes: Pushes::new(parse_pushes(input)),
+        history: Vec::new(),
+        heights: Vec::new(),
+        total,
+    };
+    driver.run(name, &mut fall);
+    fall
+}
+
+fn part1_driven(input: &str, driver: &Driver) -> usize {
+    let fall = fall_rocks(input, driver, "part1", 2023);
+    fall.heights[2022]
+}
+
+pub fn part1(input: &str) -> usize {
+    part1_driven(input, &Driver::headless())
+}
+
+pub fn part2(input: &str) -> usize {
+    part2_explained(input, &Driver::headless()).answer
+}
+
+/// The rocks fall through the driver, which renders them if asked to
+pub fn part2_explained(input: &str, driver: &Driver) -> Witnessed<usize, Cycle> {
+    let fall = fall_rocks(input, driver, "part2", 10_000);
+    tower_height(&fall.history, &fall.heights)
+}
+
+pub fn run(file: &str) {
+    let input = read_to_string(file).unwrap();
+    let driver = Driver::from_args();
+    println!("Part 1: {}", part1_driven(&input, &driver));
+    let part2 = part2_explained(&input, &driver);
+    println!("Part 2: {}", part2.answer);
+    part2.report("Part 2");
+    part2.check("Part 2");
 }
@@ -1,11 +1,10 @@
-use std::fs::read_to_string;
+use aoc2023::inputs::read_to_string;
 
 type Droplet = (usize, usize, usize);
 type Grid = Vec<Vec<Vec<State>>>;
 
-fn parse_droplets(file: &str) -> Vec<Droplet> {
-    read_to_string(file)
-        .unwrap()
+fn parse_droplets(input: &str) -> Vec<Droplet> {
+    input
         .lines()
         .map(|l| {
             let parts: Vec<_> = l.split(',').collect();
@@ -84,20 +83,17 @@ fn count_surfaces_with_state(grid: &Grid, state: State, droplets: &[Droplet]) ->
         .sum()
 }
 
-fn part1(file: &str) {
-    let droplets = parse_droplets(file);
+pub fn part1(input: &str) -> usize {
+    let droplets = parse_droplets(input);
     let mut grid = vec![vec![vec![State::Air; 25]; 25]; 25];
     droplets
         .iter()
         .for_each(|d| grid[d.0][d.1][d.2] = State::Lava);
-    println!(
-        "Part 1: {}",
-        count_surfaces_with_state(&grid, State::Air, &droplets)
-    );
+    count_surfaces_with_state(&grid, State::Air, &droplets)
 }
 
-fn part2(file: &str) {
-    let droplets = parse_droplets(file);
+pub fn part2(input: &str) -> usize {
+    let droplets = parse_droplets(input);
     let mut grid = vec![vec![vec![State::Air; 25]; 25]; 25];
     droplets
         .iter()
@@ -109,13 +105,11 @@ fn part2(file: &str) {
         flood(&mut grid, &(0, x, 0));
         flood(&mut grid, &(0, 0, x));
     }
-    println!(
-        "Part 1: {}",
-        count_surfaces_with_state(&grid, State::Air, &droplets)
-    );
+    count_surfaces_with_state(&grid, State::Air, &droplets)
 }
 
 pub fn run(file: &str) {
-    part1(file);
-    part2(file);
+    let input = read_to_string(file).unwrap();
+    println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
 }
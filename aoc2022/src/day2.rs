@@ -1,4 +1,4 @@
-use std::fs::read_to_string;
+use aoc2023::inputs::read_to_string;
 
 // No need to be fancy - we can just use a lookup table
 fn score_part2(line: &str) -> i32 {
@@ -32,11 +32,20 @@ fn score_part1(line: &str) -> i32 {
     }
 }
 
-fn total_score(file: &str, scoring: fn(&str) -> i32) -> i32 {
-    read_to_string(file).unwrap().lines().map(scoring).sum()
+fn total_score(input: &str, scoring: fn(&str) -> i32) -> i32 {
+    input.lines().map(scoring).sum()
+}
+
+pub fn part1(input: &str) -> i32 {
+    total_score(input, score_part1)
+}
+
+pub fn part2(input: &str) -> i32 {
+    total_score(input, score_part2)
 }
 
 pub fn run(file: &str) {
-    println!("Part 1: {}", total_score(file, score_part1));
-    println!("Part 2: {}", total_score(file, score_part2));
+    let input = read_to_string(file).unwrap();
+    println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
 }
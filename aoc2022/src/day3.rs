@@ -1,5 +1,6 @@
 use std::collections::HashSet;
-use std::fs::read_to_string;
+
+use aoc2023::inputs::read_to_string;
 
 use crate::BatchedIteratorExt;
 
@@ -23,9 +24,8 @@ fn find_common_item(backpacks: &[&str]) -> char {
         .expect("No items in common")
 }
 
-fn part1(file: &str) -> u32 {
-    read_to_string(file)
-        .unwrap()
+pub fn part1(input: &str) -> u32 {
+    input
         .lines()
         .map(|l| l.split_at(l.len() / 2))
         .map(|b| find_common_item(&[b.0, b.1]))
@@ -33,9 +33,8 @@ fn part1(file: &str) -> u32 {
         .sum::<u32>()
 }
 
-fn part2(file: &str) -> u32 {
-    read_to_string(file)
-        .unwrap()
+pub fn part2(input: &str) -> u32 {
+    input
         .lines()
         .batch(3)
         .map(|b| find_common_item(&b))
@@ -44,6 +43,7 @@ fn part2(file: &str) -> u32 {
 }
 
 pub fn run(file: &str) {
-    println!("Part 1: {}", part1(file));
-    println!("Part 2: {}", part2(file));
+    let input = read_to_string(file).unwrap();
+    println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
 }
@@ -1,4 +1,4 @@
-use std::fs::read_to_string;
+use aoc2023::inputs::read_to_string;
 
 #[derive(Debug)]
 struct SectionRange {
@@ -24,9 +24,8 @@ impl SectionRange {
     }
 }
 
-fn count_pairs(file: &str, predicate: fn(&(SectionRange, SectionRange)) -> bool) -> usize {
-    read_to_string(file)
-        .unwrap()
+fn count_pairs(input: &str, predicate: fn(&(SectionRange, SectionRange)) -> bool) -> usize {
+    input
         .lines()
         .map(|l| {
             let pair = l.split_once(',').unwrap();
@@ -39,10 +38,16 @@ fn count_pairs(file: &str, predicate: fn(&(SectionRange, SectionRange)) -> bool)
         .count()
 }
 
+pub fn part1(input: &str) -> usize {
+    count_pairs(input, |p| p.0.contains(&p.1) || p.1.contains(&p.0))
+}
+
+pub fn part2(input: &str) -> usize {
+    count_pairs(input, |p| p.0.overlaps(&p.1))
+}
+
 pub fn run(file: &str) {
-    println!(
-        "Part 1: {}",
-        count_pairs(file, |p| p.0.contains(&p.1) || p.1.contains(&p.0))
-    );
-    println!("Part 2: {}", count_pairs(file, |p| p.0.overlaps(&p.1)));
+    let input = read_to_string(file).unwrap();
+    println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
 }
@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
-use std::fs::read_to_string;
+
+use aoc2023::inputs::read_to_string;
 
 #[derive(Debug)]
 struct Move {
@@ -89,7 +90,7 @@ pub fn part1(content: &str) -> String {
     ship.top_crates()
 }
 
-fn part2(content: &str) -> String {
+pub fn part2(content: &str) -> String {
     let mut ship = parse_stacks(content);
     parse_moves(content).for_each(|m| ship.move_crate(m));
     ship.top_crates()
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
-use std::fs::read_to_string;
+
+use aoc2023::inputs::read_to_string;
 
 fn find_marker(line: &str, count: usize) -> usize {
     let window_start = line
@@ -10,12 +11,20 @@ fn find_marker(line: &str, count: usize) -> usize {
     window_start + count
 }
 
+pub fn part1(input: &str) -> usize {
+    find_marker(input.trim(), 4)
+}
+
+pub fn part2(input: &str) -> usize {
+    find_marker(input.trim(), 14)
+}
+
 pub fn run(file: &str) {
     let content = read_to_string(file).unwrap();
     // The actual input is only one line, but this makes validating
     // the example input easier.
     for line in content.lines() {
-        println!("Part 1: {}", find_marker(line, 4));
-        println!("Part 2: {}", find_marker(line, 14));
+        println!("Part 1: {}", part1(line));
+        println!("Part 2: {}", part2(line));
     }
 }
@@ -1,4 +1,4 @@
-use std::fs::read_to_string;
+use aoc2023::inputs::read_to_string;
 
 type NodeHandle = usize;
 
@@ -81,12 +81,12 @@ impl FileSystem {
     }
 }
 
-fn parse_filesystem(file: &str) -> FileSystem {
+fn parse_filesystem(input: &str) -> FileSystem {
     let mut fs = FileSystem::default();
     let root_handle = fs.add_node(FileSystemNode::new_dir("/".to_string(), None));
     let mut cwd = root_handle;
 
-    for line in read_to_string(file).unwrap().lines() {
+    for line in input.lines() {
         if line == "$ cd /" {
             cwd = root_handle
         } else if line == "$ cd .." {
@@ -116,9 +116,9 @@ fn parse_filesystem(file: &str) -> FileSystem {
     fs
 }
 
-fn part1(file: &str) -> u32 {
+pub fn part1(input: &str) -> u32 {
     let limit = 100000;
-    let fs = parse_filesystem(file);
+    let fs = parse_filesystem(input);
     fs.iter()
         .filter(|handle| fs.get(*handle).is_dir())
         .map(|handle| fs.size_of(handle))
@@ -126,9 +126,9 @@ fn part1(file: &str) -> u32 {
         .sum()
 }
 
-fn part2(file: &str) -> u32 {
+pub fn part2(input: &str) -> u32 {
     let total_size = 40000000;
-    let fs = parse_filesystem(file);
+    let fs = parse_filesystem(input);
     let used = fs.size_of(0);
     fs.iter()
         .filter(|handle| fs.get(*handle).is_dir())
@@ -139,6 +139,7 @@ fn part2(file: &str) -> u32 {
 }
 
 pub fn run(file: &str) {
-    println!("Part 1: {}", part1(file));
-    println!("Part 2: {}", part2(file));
+    let input = read_to_string(file).unwrap();
+    println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
 }
@@ -1,33 +1,24 @@
-use std::fs::read_to_string;
+use aoc2023::inputs::read_to_string;
+use aoc2023::Grid;
 
-fn parse_trees(file: &str) -> Vec<Vec<u32>> {
-    let content = read_to_string(file).unwrap();
-    if content.is_empty() {
-        println!("0");
-    }
-
-    content
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|p| p.to_digit(10).unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect()
+fn parse_trees(input: &str) -> Grid<u32> {
+    Grid::parse(input, |p| p.to_digit(10).unwrap())
 }
 
-fn find_max(trees: &[Vec<u32>], iter: impl Iterator<Item = (usize, usize)>) -> u32 {
-    iter.map(|(row, col)| trees[row][col]).max().unwrap()
+fn find_max(trees: &Grid<u32>, iter: impl Iterator<Item = (usize, usize)>) -> u32 {
+    iter.map(|(row, col)| trees.get(col as i64, row as i64))
+        .max()
+        .unwrap()
 }
 
-fn part1(file: &str) {
-    let trees = parse_trees(file);
-    let mut visable = trees.len() * 2 + trees[0].len() * 2 - 4;
-    let rows = trees.len();
-    let columns = trees[0].len();
+pub fn part1(input: &str) -> usize {
+    let trees = parse_trees(input);
+    let mut visable = trees.rows() * 2 + trees.cols() * 2 - 4;
+    let rows = trees.rows();
+    let columns = trees.cols();
     for row in 1..rows - 1 {
         for col in 1..columns - 1 {
-            let current = trees[row][col];
+            let current = trees.get(col as i64, row as i64);
 
             if find_max(&trees, (0..col).map(|c| (row, c))) < current || // left
                 find_max(&trees, (col+1..columns).map(|c| (row, c))) < current ||  // right
@@ -40,17 +31,17 @@ fn part1(file: &str) {
         }
     }
 
-    println!("Part 1: {}", visable);
+    visable
 }
 
 fn find_visible(
-    trees: &[Vec<u32>],
+    trees: &Grid<u32>,
     current: u32,
     total_count: usize,
     iter: impl Iterator<Item = (usize, usize)>,
 ) -> usize {
     let count = iter
-        .take_while(|(row, col)| trees[*row][*col] < current)
+        .take_while(|(row, col)| trees.get(*col as i64, *row as i64) < current)
         .count();
 
     // If the end is not visible, add one (we see the tree that we couldn't see past).
@@ -61,14 +52,14 @@ fn find_visible(
     }
 }
 
-fn part2(file: &str) {
-    let trees = parse_trees(file);
+pub fn part2(input: &str) -> usize {
+    let trees = parse_trees(input);
     let mut max_score = 0;
-    let rows = trees.len();
-    let columns = trees[0].len();
-    for row in 0..trees.len() {
-        for col in 0..trees[0].len() {
-            let current = trees[row][col];
+    let rows = trees.rows();
+    let columns = trees.cols();
+    for row in 0..rows {
+        for col in 0..columns {
+            let current = trees.get(col as i64, row as i64);
 
             let (left, left_count) = ((0..col).map(|c| (row, c)).rev(), col);
             let (right, right_count) = ((col + 1..columns).map(|c| (row, c)), columns - (col + 1));
@@ -85,10 +76,11 @@ fn part2(file: &str) {
         }
     }
 
-    println!("Part 2: {}", max_score);
+    max_score
 }
 
 pub fn run(file: &str) {
-    part1(file);
-    part2(file);
+    let input = read_to_string(file).unwrap();
+    println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
 }
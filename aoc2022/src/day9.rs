@@ -1,5 +1,6 @@
-use std::collections::HashSet;
-use std::fs::read_to_string;
+use aoc2023::inputs::read_to_string;
+use aoc2023::simulation::{Driver, Simulation};
+use aoc2023::{Coordinate, SparseGrid};
 
 #[derive(Debug)]
 enum Move {
@@ -52,10 +53,15 @@ impl Knot {
         }
     }
 
+    fn position(&self) -> Coordinate {
+        Coordinate::new(self.x as i64, self.y as i64)
+    }
+
+    /// Up is negative y, like a grid reads
     fn move_knot(&mut self, m: &Move) {
         match m {
-            Move::Up => self.y += 1,
-            Move::Down => self.y -= 1,
+            Move::Up => self.y -= 1,
+            Move::Down => self.y += 1,
             Move::Left => self.x -= 1,
             Move::Right => self.x += 1,
         }
@@ -64,7 +70,7 @@ impl Knot {
 
 struct Rope {
     knots: Vec<Knot>,
-    tail_history: HashSet<(i32, i32)>,
+    tail_history: SparseGrid<bool>,
 }
 
 impl Default for Rope {
@@ -75,8 +81,8 @@ impl Default for Rope {
 
 impl Rope {
     fn new(count: usize) -> Self {
-        let mut tail_history = HashSet::new();
-        tail_history.insert((0, 0));
+        let mut tail_history = SparseGrid::new(false);
+        tail_history.insert(Coordinate::new(0, 0), true);
         let knots = (0..count).map(|_| Knot::default()).collect::<Vec<_>>();
         Rope {
             knots,
@@ -95,18 +101,55 @@ impl Rope {
     }
 
     fn save_history(&mut self) {
-        self.tail_history
-            .insert((self.knots.last().unwrap().x, self.knots.last().unwrap().y));
+        let tail = self.knots.last().unwrap().position();
+        self.tail_history.insert(tail, true);
     }
 
     fn tail_positions(&self) -> usize {
         self.tail_history.len()
     }
+
+    fn knot_label(&self, index: usize) -> char {
+        match index {
+            0 => 'H',
+            i if i == self.knots.len() - 1 => 'T',
+            i => char::from_digit(i as u32, 10).unwrap_or('k'),
+        }
+    }
+}
+
+/// The rope following the moves of its head, one move per step.
+struct RopeSimulation<'a> {
+    rope: Rope,
+    moves: &'a [Move],
+    next: usize,
+}
+
+impl Simulation for RopeSimulation<'_> {
+    fn step(&mut self) {
+        self.rope.move_head(&self.moves[self.next]);
+        self.next += 1;
+    }
+
+    fn is_done(&self) -> bool {
+        self.next >= self.moves.len()
+    }
+
+    fn render(&self) -> String {
+        let mut picture = self.rope.tail_history.map('.', |_| '#');
+        picture.insert(Coordinate::new(0, 0), 's');
+        // The knots in front are drawn over the ones behind them
+        for (index, knot) in self.rope.knots.iter().enumerate().rev() {
+            picture.insert(knot.position(), self.rope.knot_label(index));
+        }
+
+        picture.to_string()
+    }
 }
 
-fn parse_moves(file: &str) -> Vec<Move> {
+fn parse_moves(input: &str) -> Vec<Move> {
     let mut result = Vec::new();
-    for line in read_to_string(file).unwrap().lines() {
+    for line in input.lines() {
         let (m, count) = line.split_once(' ').unwrap();
         result.extend((0..count.parse().unwrap()).map(|_| Move::from_letter(m)));
     }
@@ -114,13 +157,35 @@ fn parse_moves(file: &str) -> Vec<Move> {
     result
 }
 
-fn do_moves(moves: &[Move], mut rope: Rope) -> usize {
-    moves.iter().for_each(|m| rope.move_head(m));
-    rope.tail_positions()
+fn do_moves(driver: &Driver, name: &str, moves: &[Move], rope: Rope) -> usize {
+    let mut simulation = RopeSimulation {
+        rope,
+        moves,
+        next: 0,
+    };
+    driver.run(name, &mut simulation);
+    simulation.rope.tail_positions()
+}
+
+fn part1_driven(input: &str, driver: &Driver) -> usize {
+    do_moves(driver, "part1", &parse_moves(input), Rope::default())
+}
+
+fn part2_driven(input: &str, driver: &Driver) -> usize {
+    do_moves(driver, "part2", &parse_moves(input), Rope::new(10))
+}
+
+pub fn part1(input: &str) -> usize {
+    part1_driven(input, &Driver::headless())
+}
+
+pub fn part2(input: &str) -> usize {
+    part2_driven(input, &Driver::headless())
 }
 
 pub fn run(file: &str) {
-    let moves = parse_moves(file);
-    println!("Part 1: {}", do_moves(&moves, Rope::default()));
-    println!("Part 2: {}", do_moves(&moves, Rope::new(10)));
+    let input = read_to_string(file).unwrap();
+    let driver = Driver::from_args();
+    println!("Part 1: {}", part1_driven(&input, &driver));
+    println!("Part 2: {}", part2_driven(&input, &driver));
 }
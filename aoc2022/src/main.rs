@@ -1,6 +1,67 @@
-use std::env;
+//! Runs one of the 2022 days. The day comes first, before the optional input path: this binary
+//! used to take just the input and always ran day 18.
+
+use aoc2023::cli;
+use aoc2023::inputs;
+
+const USAGE: &str = "Usage: aoc2022 <day> [input] [options]
+
+Runs the day on the input. Built with the embed-inputs feature, the input can be left out
+to run the day on its own input.
+
+Options:
+    --impl NAME|all                  Pick an implementation, or check that they all agree
+    --explain, --check               Show the witness of the answers, or check it
+    --fuzz[=CASES] [--size=N]        Cross-check day 15 against a naive solver
+    --play[=FPS], --gif=PATH, ...    Render the simulations of days 9, 14 and 17";
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    std::process::exit(1);
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    aoc2022::day18::run(&args[1])
+    inputs::embed(inputs::embedded::YEAR_2022);
+    let args = cli::args();
+    let Some(day) = args.positional(0) else {
+        usage();
+    };
+    if args.flag("fuzz") {
+        match day {
+            "15" => aoc2022::day15::fuzz(),
+            _ => panic!("No input generator for day {}", day),
+        }
+        return;
+    }
+
+    let input = match args.positional(1) {
+        Some(path) => path.to_string(),
+        None => day
+            .parse()
+            .ok()
+            .and_then(|day| inputs::default_path(2022, day))
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_else(|| usage()),
+    };
+    match day {
+        "1" => aoc2022::day1::run(&input),
+        "2" => aoc2022::day2::run(&input),
+        "3" => aoc2022::day3::run(&input),
+        "4" => aoc2022::day4::run(&input),
+        "5" => aoc2022::day5::run(&input),
+        "6" => aoc2022::day6::run(&input),
+        "7" => aoc2022::day7::run(&input),
+        "8" => aoc2022::day8::run(&input),
+        "9" => aoc2022::day9::run(&input),
+        "10" => aoc2022::day10::run(&input),
+        "11" => aoc2022::day11::run(&input),
+        "12" => aoc2022::day12::run(&input),
+        "13" => aoc2022::day13::run(&input),
+        "14" => aoc2022::day14::run(&input),
+        "15" => aoc2022::day15::run(&input),
+        "16" => aoc2022::day16::run(&input),
+        "17" => aoc2022::day17::run(&input),
+        "18" => aoc2022::day18::run(&input),
+        _ => usage(),
+    }
 }
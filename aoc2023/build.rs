@@ -0,0 +1,158 @@
+//! With the `embed-inputs` feature, the files under `inputs/` are compiled into the binaries.
+//! The tables are generated into `$OUT_DIR/embedded_inputs.rs` and read by `inputs.rs`.
+//!
+//! The list of days is generated into `$OUT_DIR/days.rs` from the day files of both years, and
+//! read by `days.rs`.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn files(dir: &Path, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if path.is_dir() {
+            files(&path, found);
+        } else {
+            found.push(path);
+        }
+    }
+}
+
+/// The years and days that get a table, whether or not there are inputs for them, so that a
+/// binary naming its table builds either way
+const YEARS: [u32; 2] = [2022, 2023];
+const DAYS: u32 = 25;
+
+fn table(name: &str, files: &[&(String, PathBuf)]) -> String {
+    let mut table = format!("pub static {}: &[(&str, &[u8])] = &[\n", name);
+    for (name, path) in files {
+        table.push_str(&format!("    ({:?}, include_bytes!({:?})),\n", name, path));
+    }
+    table.push_str("];\n");
+    table
+}
+
+/// The parts a day answers, from the `//! Answers: Part 1, Part 2` line of its file. Most days
+/// don't have one and answer both parts.
+fn parts(source: &str, path: &Path) -> Vec<&'static str> {
+    let Some(line) = source
+        .lines()
+        .find_map(|line| line.strip_prefix("//! Answers:"))
+    else {
+        return vec!["Part::One", "Part::Two"];
+    };
+
+    line.split(',')
+        .map(|part| match part.trim() {
+            "Part 1" => "Part::One",
+            "Part 2" => "Part::Two",
+            "Result" => "Part::Combined",
+            other => panic!("{}: unknown answer {:?}", path.display(), other),
+        })
+        .collect()
+}
+
+/// Every `dayN.rs` in the directory, with the parts it answers
+fn days_in(dir: &Path, year: u32, days: &mut Vec<(u32, u32, Vec<&'static str>)>) {
+    println!("cargo:rerun-if-changed={}", dir.display());
+    let mut paths = Vec::new();
+    files(dir, &mut paths);
+    for path in paths {
+        let Some(day) = path.file_name().and_then(|name| {
+            name.to_str()?
+                .strip_prefix("day")?
+                .strip_suffix(".rs")?
+                .parse()
+                .ok()
+        }) else {
+            continue;
+        };
+        let source = fs::read_to_string(&path).unwrap();
+        days.push((year, day, parts(&source, &path)));
+    }
+}
+
+fn days() -> String {
+    // The 2022 days are modules of the aoc2022 library, the 2023 days binaries of their own
+    let manifest = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let mut days = Vec::new();
+    days_in(&manifest.join("../aoc2022/src"), 2022, &mut days);
+    days_in(&manifest.join("src/bin"), 2023, &mut days);
+    days.sort();
+
+    let mut out = String::from("pub const DAYS: &[Day] = &[\n");
+    for (year, day, parts) in days {
+        out.push_str(&format!(
+            "    Day {{ year: {}, day: {}, parts: &[{}] }},\n",
+            year,
+            day,
+            parts.join(", ")
+        ));
+    }
+    out.push_str("];\n");
+    out
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("days.rs"), days()).unwrap();
+
+    // Named like `2023/day16.txt`, relative to the inputs directory
+    let mut found = Vec::new();
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("../inputs");
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        println!("cargo:rerun-if-changed={}", root.display());
+        let mut paths = Vec::new();
+        files(&root, &mut paths);
+        paths.sort();
+
+        for path in paths {
+            println!("cargo:rerun-if-changed={}", path.display());
+            let name = path
+                .strip_prefix(&root)
+                .unwrap()
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            found.push((name, path.canonicalize().unwrap()));
+        }
+    }
+
+    // Only the names are shared by every binary, the contents are split into a table per year
+    // and per day so that a binary only links the ones it reads
+    let mut out = String::from("pub static NAMES: &[&str] = &[\n");
+    for (name, _) in &found {
+        out.push_str(&format!("    {:?},\n", name));
+    }
+    out.push_str("];\n");
+
+    for year in YEARS {
+        let in_year = |(name, _): &&(String, PathBuf)| name.starts_with(&format!("{}/", year));
+        let files = found.iter().filter(in_year).collect::<Vec<_>>();
+        out.push_str(&table(&format!("YEAR_{}", year), &files));
+
+        for day in 1..=DAYS {
+            // 2023 keeps `day16.txt` and `day16_example.txt`, 2022 a `day16/` directory
+            let prefixes = [
+                format!("{}/day{}.", year, day),
+                format!("{}/day{}_", year, day),
+                format!("{}/day{}/", year, day),
+            ];
+            let files = files
+                .iter()
+                .copied()
+                .filter(|(name, _)| prefixes.iter().any(|p| name.starts_with(p.as_str())))
+                .collect::<Vec<_>>();
+            out.push_str(&table(&format!("DAY_{}_{}", year, day), &files));
+        }
+    }
+
+    fs::write(out_dir.join("embedded_inputs.rs"), out).unwrap();
+}
@@ -0,0 +1,151 @@
+//! Turning character grids into images, and sequences of them into animated GIFs.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use crate::Grid;
+
+pub type Rgb = [u8; 3];
+
+/// Maps every character of a grid to a color. Characters without a color of their own get the
+/// fallback color, and padding around smaller frames gets the background.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    colors: Vec<Rgb>,
+    lookup: HashMap<char, u8>,
+}
+
+const BACKGROUND: u8 = 0;
+const FALLBACK: u8 = 1;
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::new([15, 15, 35], [200, 200, 200])
+            .with('.', [15, 15, 35])
+            .with('#', [120, 120, 120])
+    }
+}
+
+impl Palette {
+    pub fn new(background: Rgb, fallback: Rgb) -> Self {
+        Self {
+            colors: vec![background, fallback],
+            lookup: HashMap::new(),
+        }
+    }
+
+    pub fn with(mut self, c: char, color: Rgb) -> Self {
+        let index = match self.colors.iter().position(|&existing| existing == color) {
+            Some(index) => index,
+            None => {
+                assert!(
+                    self.colors.len() < 256,
+                    "A GIF palette has at most 256 colors"
+                );
+                self.colors.push(color);
+                self.colors.len() - 1
+            }
+        };
+        self.lookup.insert(c, index as u8);
+        self
+    }
+
+    fn index(&self, c: char) -> u8 {
+        *self.lookup.get(&c).unwrap_or(&FALLBACK)
+    }
+
+    /// Render a grid to an image where every cell is a `scale` x `scale` square
+    pub fn render(&self, grid: &Grid, scale: usize) -> Image {
+        self.render_sized(grid, scale, grid.cols(), grid.rows())
+    }
+
+    fn render_sized(&self, grid: &Grid, scale: usize, cols: usize, rows: usize) -> Image {
+        let width = cols * scale;
+        let mut pixels = vec![BACKGROUND; width * rows * scale];
+        for y in 0..grid.rows() {
+            for x in 0..grid.cols() {
+                let color = self.index(grid.get(x as i64, y as i64));
+                for py in y * scale..(y + 1) * scale {
+                    pixels[py * width + x * scale..py * width + (x + 1) * scale].fill(color);
+                }
+            }
+        }
+
+        Image {
+            width,
+            height: rows * scale,
+            pixels,
+        }
+    }
+}
+
+/// An image with colors given as indices into the palette it was rendered with
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+/// A sequence of grids played back as an animated GIF. Frames don't need to have the same size,
+/// smaller ones are padded with the background color.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    palette: Palette,
+    scale: usize,
+    /// Time between frames, in hundredths of a second
+    delay: u16,
+    frames: Vec<Grid>,
+}
+
+impl Animation {
+    pub fn new(palette: Palette, scale: usize, delay: u16) -> Self {
+        Self {
+            palette,
+            scale,
+            delay,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, grid: Grid) {
+        self.frames.push(grid);
+    }
+
+    /// Add a frame from any character grid, such as the rendering of a simulation
+    pub fn push_text(&mut self, text: &str) {
+        self.push(Grid::from_raw(text));
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), gif::EncodingError> {
+        let cols = self.frames.iter().map(|f| f.cols()).max().unwrap_or(0);
+        let rows = self.frames.iter().map(|f| f.rows()).max().unwrap_or(0);
+        let (width, height) = (cols * self.scale, rows * self.scale);
+        let too_large = || std::io::Error::other("The animation is too large for a GIF");
+        let width = u16::try_from(width).map_err(|_| too_large())?;
+        let height = u16::try_from(height).map_err(|_| too_large())?;
+
+        let palette = self.palette.colors.concat();
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = gif::Encoder::new(file, width, height, &palette)?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        for grid in self.frames.iter() {
+            let image = self.palette.render_sized(grid, self.scale, cols, rows);
+            let mut frame = gif::Frame::from_indexed_pixels(width, height, image.pixels, None);
+            frame.delay = self.delay;
+            encoder.write_frame(&frame)?;
+        }
+
+        Ok(())
+    }
+}
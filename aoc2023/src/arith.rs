@@ -0,0 +1,91 @@
+//! Arithmetic helpers for the places where the puzzle inputs push us close to the integer limits.
+//!
+//! Without any features these are just the plain operators. With the `checked-arith` feature
+//! every operation is checked, and an overflow panics with the day and the operation that caused
+//! it instead of silently wrapping in release builds.
+
+use std::fmt::Display;
+use std::ops::{Add, Mul, Sub};
+
+/// Integer types that we can do (optionally checked) arithmetic on.
+pub trait Integer:
+    Copy + Display + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i32, i64, i128, u32, u64, u128, usize);
+
+/// The name of the day that is currently running. Every 2023 day is its own binary, so the
+/// binary name tells us the day - the 2022 days include the day in the operation instead.
+#[cfg(feature = "checked-arith")]
+fn running_day() -> String {
+    crate::cli::program().unwrap_or_else(|| "unknown".into())
+}
+
+#[cfg(feature = "checked-arith")]
+fn overflow<T: Integer>(lhs: T, op: char, rhs: T, operation: &str) -> ! {
+    panic!(
+        "{}: arithmetic overflow in {}: {} {} {}",
+        running_day(),
+        operation,
+        lhs,
+        op,
+        rhs
+    )
+}
+
+#[cfg(feature = "checked-arith")]
+pub fn add<T: Integer>(lhs: T, rhs: T, operation: &str) -> T {
+    lhs.checked_add(rhs)
+        .unwrap_or_else(|| overflow(lhs, '+', rhs, operation))
+}
+
+#[cfg(feature = "checked-arith")]
+pub fn sub<T: Integer>(lhs: T, rhs: T, operation: &str) -> T {
+    lhs.checked_sub(rhs)
+        .unwrap_or_else(|| overflow(lhs, '-', rhs, operation))
+}
+
+#[cfg(feature = "checked-arith")]
+pub fn mul<T: Integer>(lhs: T, rhs: T, operation: &str) -> T {
+    lhs.checked_mul(rhs)
+        .unwrap_or_else(|| overflow(lhs, '*', rhs, operation))
+}
+
+#[cfg(not(feature = "checked-arith"))]
+pub fn add<T: Integer>(lhs: T, rhs: T, _operation: &str) -> T {
+    lhs + rhs
+}
+
+#[cfg(not(feature = "checked-arith"))]
+pub fn sub<T: Integer>(lhs: T, rhs: T, _operation: &str) -> T {
+    lhs - rhs
+}
+
+#[cfg(not(feature = "checked-arith"))]
+pub fn mul<T: Integer>(lhs: T, rhs: T, _operation: &str) -> T {
+    lhs * rhs
+}
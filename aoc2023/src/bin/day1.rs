@@ -1,3 +1,7 @@
+//! Answers: Part 2
+//!
+//! Only the part 2 solution was kept, it covers the digits of part 1 as well.
+
 use std::collections::HashMap;
 
 use aoc2023::{map, read_input_lines};
@@ -44,6 +48,7 @@ fn extract_number(line: String) -> i32 {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_1);
     let total = read_input_lines()
         .into_iter()
         .map(extract_number)
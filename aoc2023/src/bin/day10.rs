@@ -92,8 +92,8 @@ fn search(grid: &mut Grid) -> Vec<(i64, i64)> {
     let start = grid.find('S').expect("No start found");
     // Let's search through all possible ones instead of making S a special case
     for n in "LF|-J7".chars() {
-        grid.update(start.0 as i64, start.1 as i64, n);
-        if let Some(res) = search_from(grid, start.0 as i64, start.1 as i64) {
+        grid[start] = n;
+        if let Some(res) = search_from(grid, start.x, start.y) {
             return res;
         }
     }
@@ -101,6 +101,7 @@ fn search(grid: &mut Grid) -> Vec<(i64, i64)> {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_10);
     let mut grid = Grid::from_input();
     let mut path = search(&mut grid);
     println!("Part 1: {}", path.len() / 2);
@@ -11,9 +11,9 @@ impl Expanded {
     fn from_grid(grid: &Grid) -> Self {
         let mut occupied_columns = HashSet::new();
         let mut occupied_rows = HashSet::new();
-        for (column, row) in grid.find_all('#').iter() {
-            occupied_columns.insert(*column);
-            occupied_rows.insert(*row);
+        for galaxy in grid.find_all('#') {
+            occupied_columns.insert(galaxy.x as usize);
+            occupied_rows.insert(galaxy.y as usize);
         }
 
         let mut rows = vec![];
@@ -40,26 +40,26 @@ fn solve(expansion_ratio: usize) -> usize {
 
     let galaxies = grid.find_all('#');
     let mut distances = vec![];
-    for (x, y) in galaxies.iter() {
-        for (x2, y2) in galaxies.iter() {
-            if x == x2 && y == y2 {
+    for a in galaxies.iter() {
+        for b in galaxies.iter() {
+            if a == b {
                 continue;
             }
             let mut num_expanded = 0;
             for row in expanded.rows.iter() {
-                if within_range(*row as i64, *y as i64, *y2 as i64) {
+                if within_range(*row as i64, a.y, b.y) {
                     num_expanded += 1;
                 }
             }
 
             for column in expanded.columns.iter() {
-                if within_range(*column as i64, *x as i64, *x2 as i64) {
+                if within_range(*column as i64, a.x, b.x) {
                     num_expanded += 1;
                 }
             }
 
             distances.push(
-                max(y2, y) - min(y2, y) + max(x2, x) - min(x2, x)
+                (max(b.y, a.y) - min(b.y, a.y) + max(b.x, a.x) - min(b.x, a.x)) as usize
                     + num_expanded * (expansion_ratio - 1),
             );
         }
@@ -69,6 +69,7 @@ fn solve(expansion_ratio: usize) -> usize {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_11);
     println!("Part 1: {}", solve(2));
     println!("Part 2: {}", solve(1000000));
 }
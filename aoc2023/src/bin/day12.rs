@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use aoc2023::parallel::par_map_reduce;
 use aoc2023::{read_input_lines, repeat};
 
 fn search(
@@ -51,6 +52,7 @@ fn parse<S: AsRef<str>>(line: S) -> (Vec<char>, Vec<u64>) {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_12);
     println!(
         "Part 1: {}",
         read_input_lines()
@@ -63,22 +65,27 @@ fn main() {
             .sum::<u64>()
     );
 
+    let records = read_input_lines()
+        .iter()
+        .map(parse)
+        .map(|(mut line, blocks)| {
+            // Nasty... should have kept it as a string
+            line.push('?');
+            line = repeat(line, 5);
+            line.pop().unwrap();
+            (line, repeat(blocks, 5))
+        })
+        .collect::<Vec<_>>();
     println!(
         "Part 2: {}",
-        read_input_lines()
-            .iter()
-            .map(parse)
-            .map(|(mut line, blocks)| {
-                // Nasty... should have kept it as a string
-                line.push('?');
-                line = repeat(line, 5);
-                line.pop().unwrap();
-                (line, repeat(blocks, 5))
-            })
-            .map(|(line, blocks)| {
+        par_map_reduce(
+            &records,
+            |(line, blocks)| {
                 let mut dp = HashMap::new();
-                search(&mut dp, &line, 0, &blocks)
-            })
-            .sum::<u64>()
+                search(&mut dp, line, 0, blocks)
+            },
+            |a, b| a + b,
+        )
+        .unwrap_or(0)
     );
 }
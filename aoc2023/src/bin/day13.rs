@@ -1,3 +1,6 @@
+use std::fmt;
+
+use aoc2023::witness::{Check, Witnessed};
 use aoc2023::{min, read_input_content, Grid};
 
 fn row_diffs(idx1: usize, idx2: usize, grid: &Grid) -> usize {
@@ -10,16 +13,16 @@ fn row_diffs(idx1: usize, idx2: usize, grid: &Grid) -> usize {
     diffs
 }
 
-fn find_matches(grid: Grid, allowed_diffs: usize, multiplier: usize) -> Option<usize> {
+fn find_matches(grid: &Grid, allowed_diffs: usize) -> Option<usize> {
     for i in 1..grid.rows() {
         let mut diff_buffer = allowed_diffs;
-        let diffs = row_diffs(i - 1, i, &grid);
+        let diffs = row_diffs(i - 1, i, grid);
         if diffs <= diff_buffer {
             diff_buffer -= diffs;
             let mut found = true;
             let m = min(i, grid.rows() - i);
             for j in 1..m {
-                let diffs = row_diffs(i - j - 1, i + j, &grid);
+                let diffs = row_diffs(i - j - 1, i + j, grid);
                 if diffs > diff_buffer {
                     found = false;
                     break;
@@ -29,7 +32,7 @@ fn find_matches(grid: Grid, allowed_diffs: usize, multiplier: usize) -> Option<u
             }
 
             if found && diff_buffer == 0 {
-                return Some(i * multiplier);
+                return Some(i);
             }
         }
     }
@@ -37,28 +40,149 @@ fn find_matches(grid: Grid, allowed_diffs: usize, multiplier: usize) -> Option<u
     None
 }
 
-fn main() {
-    let input = read_input_content();
-    let res = input
-        .split("\n\n")
-        .map(Grid::from_raw)
-        .map(|g| {
-            find_matches(g.clone(), 0, 100)
-                .or_else(|| find_matches(g.transpose(), 0, 1))
-                .unwrap()
-        })
-        .sum::<usize>();
-    println!("Part 1: {}", res);
+/// Where a pattern is mirrored, `index` is the number of rows above (or columns left of) the line
+#[derive(Debug, Clone, Copy)]
+struct Reflection {
+    index: usize,
+    horizontal: bool,
+}
+
+impl Reflection {
+    fn find(grid: &Grid, allowed_diffs: usize) -> Option<Self> {
+        find_matches(grid, allowed_diffs)
+            .map(|index| Reflection {
+                index,
+                horizontal: true,
+            })
+            .or_else(|| {
+                find_matches(&grid.transpose(), allowed_diffs).map(|index| Reflection {
+                    index,
+                    horizontal: false,
+                })
+            })
+    }
+
+    fn summary(&self) -> usize {
+        if self.horizontal {
+            self.index * 100
+        } else {
+            self.index
+        }
+    }
+
+    /// The number of cells that don't match their mirror image
+    fn mismatches(&self, grid: &Grid) -> usize {
+        let (rows, cols) = (grid.rows() as i64, grid.cols() as i64);
+        let mut mismatches = 0;
+        for y in 0..rows {
+            for x in 0..cols {
+                let (mirror_x, mirror_y) = match self.horizontal {
+                    true => (x, 2 * self.index as i64 - 1 - y),
+                    false => (2 * self.index as i64 - 1 - x, y),
+                };
+                if grid.is_valid(mirror_x, mirror_y)
+                    && grid.get(x, y) != grid.get(mirror_x, mirror_y)
+                {
+                    mismatches += 1;
+                }
+            }
+        }
+        // Every mismatch was counted from both sides of the line
+        mismatches / 2
+    }
+}
+
+/// The reflection found in every pattern, and the number of smudges it took
+struct Reflections {
+    patterns: Vec<(Grid, Reflection)>,
+    smudges: usize,
+}
 
-    let res = input
-        .split("\n\n")
-        .map(Grid::from_raw)
+impl Check<usize> for Reflections {
+    fn check(&self, answer: &usize) -> Result<(), String> {
+        for (i, (grid, reflection)) in self.patterns.iter().enumerate() {
+            let size = match reflection.horizontal {
+                true => grid.rows(),
+                false => grid.cols(),
+            };
+            if reflection.index == 0 || reflection.index >= size {
+                return Err(format!("Pattern {} has its line on the edge", i + 1));
+            }
+            let mismatches = reflection.mismatches(grid);
+            if mismatches != self.smudges {
+                return Err(format!(
+                    "Pattern {} has {} cells that don't mirror, not {}",
+                    i + 1,
+                    mismatches,
+                    self.smudges
+                ));
+            }
+        }
+
+        let total = self
+            .patterns
+            .iter()
+            .map(|(_, r)| r.summary())
+            .sum::<usize>();
+        if total != *answer {
+            return Err(format!("The reflections sum to {}, not {}", total, answer));
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Reflections {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (_, reflection)) in self.patterns.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let (line, unit) = match reflection.horizontal {
+                true => ("horizontal", "rows"),
+                false => ("vertical", "columns"),
+            };
+            write!(
+                f,
+                "Pattern {}: {} line between {} {} and {} ({})",
+                i + 1,
+                line,
+                unit,
+                reflection.index,
+                reflection.index + 1,
+                reflection.summary()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn summarize(input: &str, allowed_diffs: usize) -> Witnessed<usize, Reflections> {
+    let patterns = Grid::parse_many(input, |c| c)
+        .into_iter()
         .map(|g| {
-            let m = find_matches(g.clone(), 1, 100)
-                .or_else(|| find_matches(g.transpose(), 1, 1))
-                .unwrap();
-            m
+            let reflection = Reflection::find(&g, allowed_diffs).unwrap();
+            (g, reflection)
         })
-        .sum::<usize>();
-    println!("Part 2: {}", res)
+        .collect::<Vec<_>>();
+    let total = patterns.iter().map(|(_, r)| r.summary()).sum();
+    let reflections = Reflections {
+        patterns,
+        smudges: allowed_diffs,
+    };
+    Witnessed::new(total, reflections)
+}
+
+fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_13);
+    let input = read_input_content();
+    let res = summarize(&input, 0);
+    println!("Part 1: {}", res.answer);
+    res.report("Part 1");
+    res.check("Part 1");
+
+    let res = summarize(&input, 1);
+    println!("Part 2: {}", res.answer);
+    res.report("Part 2");
+    res.check("Part 2");
 }
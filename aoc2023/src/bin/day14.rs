@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use aoc2023::animation::Palette;
+use aoc2023::simulation::{Driver, Simulation};
 use aoc2023::Grid;
 
 fn roll_north(grid: &mut Grid) {
@@ -9,7 +11,7 @@ fn roll_north(grid: &mut Grid) {
             if grid.get(col as i64, row as i64) == 'O' {
                 grid.update(col as i64, row as i64, '.');
                 grid.update(col as i64, current_top as i64, 'O');
-                current_top = current_top + 1;
+                current_top += 1;
             } else if grid.get(col as i64, row as i64) == '#' {
                 current_top = row + 1;
             }
@@ -24,7 +26,7 @@ fn roll_west(grid: &mut Grid) {
             if grid.get(col as i64, row as i64) == 'O' {
                 grid.update(col as i64, row as i64, '.');
                 grid.update(current_left as i64, row as i64, 'O');
-                current_left = current_left + 1;
+                current_left += 1;
             } else if grid.get(col as i64, row as i64) == '#' {
                 current_left = col + 1;
             }
@@ -39,9 +41,7 @@ fn roll_south(grid: &mut Grid) {
             if grid.get(col as i64, row as i64) == 'O' {
                 grid.update(col as i64, row as i64, '.');
                 grid.update(col as i64, current_bottom as i64, 'O');
-                if current_bottom > 0 {
-                    current_bottom = current_bottom - 1;
-                }
+                current_bottom = current_bottom.saturating_sub(1);
             } else if grid.get(col as i64, row as i64) == '#' && row > 0 {
                 current_bottom = row - 1;
             }
@@ -56,9 +56,7 @@ fn roll_east(grid: &mut Grid) {
             if grid.get(col as i64, row as i64) == 'O' {
                 grid.update(col as i64, row as i64, '.');
                 grid.update(current_right as i64, row as i64, 'O');
-                if current_right > 0 {
-                    current_right = current_right - 1;
-                }
+                current_right = current_right.saturating_sub(1);
             } else if grid.get(col as i64, row as i64) == '#' && col > 0 {
                 current_right = col - 1;
             }
@@ -87,24 +85,59 @@ fn score(grid: &Grid) -> u64 {
     score
 }
 
-fn part2() {
-    let mut grid = Grid::from_input();
-    let mut cache = HashMap::new();
-    let cycles = 1000000000;
-    for i in 0..cycles {
-        cycle(&mut grid);
-        if let Some(key) = cache.get(&format!("{}", grid)[..]) {
+/// The platform being spun, one full cycle per step. Once a state repeats we know where the last
+/// cycle ends up and skip straight there.
+struct SpinCycle {
+    grid: Grid,
+    /// The round rocks after every cycle, by when that was
+    cache: HashMap<Vec<u64>, usize>,
+    completed: usize,
+    cycles: usize,
+    done: bool,
+}
+
+impl Simulation for SpinCycle {
+    fn step(&mut self) {
+        let i = self.completed;
+        cycle(&mut self.grid);
+        self.completed += 1;
+        let rocks = self.grid.bitmask(&'O');
+        if let Some(key) = self.cache.get(&rocks) {
             let repeat_len = i - key;
-            let remaining = ((cycles - i) % repeat_len) - 1;
+            let remaining = ((self.cycles - i) % repeat_len) - 1;
             for _ in 0..remaining {
-                cycle(&mut grid);
+                cycle(&mut self.grid);
             }
-            break;
+            self.done = true;
+            return;
         }
 
-        cache.insert(format!("{}", grid), i);
+        self.cache.insert(rocks, i);
     }
-    println!("{}", score(&grid));
+
+    fn is_done(&self) -> bool {
+        self.done || self.completed >= self.cycles
+    }
+
+    fn render(&self) -> String {
+        format!("{}", self.grid)
+    }
+
+    fn palette(&self) -> Palette {
+        Palette::default().with('O', [200, 120, 60])
+    }
+}
+
+fn part2() {
+    let mut spin = SpinCycle {
+        grid: Grid::from_input(),
+        cache: HashMap::new(),
+        completed: 0,
+        cycles: 1000000000,
+        done: false,
+    };
+    Driver::from_args().run("part2", &mut spin);
+    println!("{}", score(&spin.grid));
 }
 
 fn part1() {
@@ -114,6 +147,7 @@ fn part1() {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_14);
     part1();
     part2();
 }
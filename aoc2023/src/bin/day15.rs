@@ -58,6 +58,7 @@ fn part2() {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_15);
     part1();
     part2();
 }
@@ -1,29 +1,44 @@
 use std::collections::{HashSet, VecDeque};
 
+use aoc2023::animation::Palette;
+use aoc2023::parallel::par_map_reduce;
+use aoc2023::simulation::{Driver, Simulation};
 use aoc2023::{Coordinate, Direction, Grid};
 
-fn traverse(grid: &Grid, start: (Direction, Coordinate)) -> (HashSet<Coordinate>, Grid) {
-    let mut energized = HashSet::new();
-    let mut vgrid = Grid::from_size(grid.cols(), grid.rows(), '.');
-    let mut visited = HashSet::new();
+/// Beams spreading through the contraption. Every step moves all the beams one tile.
+struct Beams<'a> {
+    grid: &'a Grid,
+    energized: HashSet<Coordinate>,
+    vgrid: Grid,
+    visited: HashSet<(Direction, Coordinate)>,
+    queue: VecDeque<(Direction, Coordinate)>,
+}
 
-    let mut queue = VecDeque::new();
-    queue.push_back(start);
+impl<'a> Beams<'a> {
+    fn new(grid: &'a Grid, start: (Direction, Coordinate)) -> Self {
+        Self {
+            grid,
+            energized: HashSet::new(),
+            vgrid: Grid::from_size(grid.width(), grid.height(), '.'),
+            visited: HashSet::new(),
+            queue: VecDeque::from(vec![start]),
+        }
+    }
 
-    while !queue.is_empty() {
-        let (direction, coordinate) = queue.pop_front().unwrap();
-        if visited.contains(&(direction, coordinate)) {
-            continue;
+    fn advance(&mut self, direction: Direction, coordinate: Coordinate) {
+        let grid = self.grid;
+        if self.visited.contains(&(direction, coordinate)) {
+            return;
         }
 
-        visited.insert((direction, coordinate));
+        self.visited.insert((direction, coordinate));
         if coordinate.x >= 0
             && coordinate.y >= 0
             && coordinate.x < grid.cols() as i64
             && coordinate.y < grid.rows() as i64
         {
-            energized.insert(coordinate);
-            vgrid.update(coordinate.x, coordinate.y, '#');
+            self.energized.insert(coordinate);
+            self.vgrid[coordinate] = '#';
         }
 
         let next = match coordinate.navigate(
@@ -32,10 +47,11 @@ fn traverse(grid: &Grid, start: (Direction, Coordinate)) -> (HashSet<Coordinate>
             (grid.rows() - 1) as i64,
         ) {
             Some(next) => next,
-            None => continue,
+            None => return,
         };
 
-        match grid.get(next.x, next.y) {
+        let queue = &mut self.queue;
+        match grid[next] {
             '.' => queue.push_back((direction, next)),
             '/' => match direction {
                 Direction::Up => queue.push_back((Direction::Right, next)),
@@ -66,41 +82,84 @@ fn traverse(grid: &Grid, start: (Direction, Coordinate)) -> (HashSet<Coordinate>
             _ => unreachable!(),
         }
     }
+}
+
+impl Simulation for Beams<'_> {
+    fn step(&mut self) {
+        for _ in 0..self.queue.len() {
+            let (direction, coordinate) = self.queue.pop_front().unwrap();
+            self.advance(direction, coordinate);
+        }
+    }
 
-    (energized, vgrid)
+    fn is_done(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for y in 0..self.grid.rows() as i64 {
+            for x in 0..self.grid.cols() as i64 {
+                out.push(match self.grid.get(x, y) {
+                    '.' => self.vgrid.get(x, y),
+                    mirror => mirror,
+                });
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    fn palette(&self) -> Palette {
+        Palette::new([15, 15, 35], [90, 140, 220])
+            .with('.', [15, 15, 35])
+            .with('#', [250, 220, 80])
+    }
+}
+
+fn traverse(
+    grid: &Grid,
+    start: (Direction, Coordinate),
+    driver: &Driver,
+) -> (HashSet<Coordinate>, Grid) {
+    let mut beams = Beams::new(grid, start);
+    driver.run("beams", &mut beams);
+    (beams.energized, beams.vgrid)
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_16);
     let grid = Grid::from_input();
-    let (energized, _) = traverse(&grid, (Direction::Right, Coordinate::new(-1, 0)));
+    let (energized, _) = traverse(
+        &grid,
+        (Direction::Right, Coordinate::new(-1, 0)),
+        &Driver::from_args(),
+    );
     println!("Part 1: {}", energized.len());
 
-    let mut res = 0;
-    for row in 0..grid.rows() {
-        let (energized, _) = traverse(&grid, (Direction::Right, Coordinate::new(-1, row as i64)));
-        res = aoc2023::max(res, energized.len());
-        let (energized, _) = traverse(
-            &grid,
-            (
-                Direction::Left,
-                Coordinate::new(grid.cols() as i64, row as i64),
-            ),
-        );
-        res = aoc2023::max(res, energized.len());
-    }
-
-    for col in 0..grid.cols() {
-        let (energized, _) = traverse(&grid, (Direction::Down, Coordinate::new(col as i64, -1)));
-        res = aoc2023::max(res, energized.len());
-        let (energized, _) = traverse(
-            &grid,
-            (
-                Direction::Up,
-                Coordinate::new(col as i64, grid.rows() as i64),
-            ),
-        );
-        res = aoc2023::max(res, energized.len());
-    }
+    let (rows, cols) = (grid.rows() as i64, grid.cols() as i64);
+    let starts = (0..rows)
+        .flat_map(|row| {
+            [
+                (Direction::Right, Coordinate::new(-1, row)),
+                (Direction::Left, Coordinate::new(cols, row)),
+            ]
+        })
+        .chain((0..cols).flat_map(|col| {
+            [
+                (Direction::Down, Coordinate::new(col, -1)),
+                (Direction::Up, Coordinate::new(col, rows)),
+            ]
+        }))
+        .collect::<Vec<_>>();
+    let headless = Driver::headless();
+    let res = par_map_reduce(
+        &starts,
+        |start| traverse(&grid, *start, &headless).0.len(),
+        aoc2023::max,
+    )
+    .unwrap();
 
     println!("Part 2: {}", res);
 }
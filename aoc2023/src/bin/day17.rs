@@ -1,40 +1,99 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt;
 
+use aoc2023::search::astar_multi;
+use aoc2023::witness::{Check, Witnessed};
 use aoc2023::{Coordinate, Direction, Grid};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct State {
-    coordinate: Coordinate,
-    direction: Direction,
-    distance: usize,
-    num_straight: usize,
+/// Where the crucible is, the direction it's moving in and how many blocks it has moved straight
+type State = (Coordinate, Direction, usize);
+
+/// How far a crucible has to, and is allowed to, move in a straight line
+struct Crucible {
+    allowed_directions: fn(usize, Direction) -> Vec<Direction>,
+    min_straight: usize,
+    max_straight: usize,
 }
 
-impl State {
-    fn new(
-        coordinate: Coordinate,
-        direction: Direction,
-        distance: usize,
-        num_straight: usize,
-    ) -> Self {
-        Self {
-            coordinate,
-            direction,
-            distance,
-            num_straight,
-        }
-    }
+const CRUCIBLE: Crucible = Crucible {
+    allowed_directions: allowed_directions_crucible,
+    min_straight: 1,
+    max_straight: 3,
+};
+
+const ULTRA_CRUCIBLE: Crucible = Crucible {
+    allowed_directions: allowed_directions_ultra_crucible,
+    min_straight: 4,
+    max_straight: 10,
+};
+
+/// The blocks the crucible passes through, from the start to the goal
+struct CruciblePath {
+    grid: Grid<usize>,
+    path: Vec<Coordinate>,
+    min_straight: usize,
+    max_straight: usize,
 }
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.distance.cmp(&self.distance)
+impl Check<usize> for CruciblePath {
+    fn check(&self, answer: &usize) -> Result<(), String> {
+        let goal = Coordinate::new(self.grid.cols() as i64 - 1, self.grid.rows() as i64 - 1);
+        if self.path.first() != Some(&Coordinate::new(0, 0)) || self.path.last() != Some(&goal) {
+            return Err("The path doesn't go from the top left to the bottom right".into());
+        }
+
+        // Split the path into the straight lines it's made of
+        let mut lines: Vec<((i64, i64), usize)> = Vec::new();
+        for step in self.path.windows(2) {
+            let direction = (step[1].x - step[0].x, step[1].y - step[0].y);
+            if direction.0.abs() + direction.1.abs() != 1 {
+                return Err(format!(
+                    "{:?} to {:?} isn't a single move",
+                    step[0], step[1]
+                ));
+            }
+            match lines.last_mut() {
+                Some((last, length)) if *last == direction => *length += 1,
+                Some((last, _)) if (last.0 + direction.0, last.1 + direction.1) == (0, 0) => {
+                    return Err(format!("The crucible reverses at {:?}", step[0]));
+                }
+                _ => lines.push((direction, 1)),
+            }
+        }
+        if let Some((_, length)) = lines
+            .iter()
+            .find(|(_, l)| *l < self.min_straight || *l > self.max_straight)
+        {
+            return Err(format!(
+                "The crucible moves {} blocks in a straight line, it has to be {} to {}",
+                length, self.min_straight, self.max_straight
+            ));
+        }
+
+        let heat_loss = self.path[1..].iter().map(|c| self.grid[*c]).sum::<usize>();
+        if heat_loss != *answer {
+            return Err(format!("The path loses {} heat, not {}", heat_loss, answer));
+        }
+
+        Ok(())
     }
 }
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+impl fmt::Display for CruciblePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut grid = self
+            .grid
+            .map(|loss| char::from_digit(*loss as u32, 10).unwrap());
+        for step in self.path.windows(2) {
+            let arrow = match (step[1].x - step[0].x, step[1].y - step[0].y) {
+                (1, _) => '>',
+                (-1, _) => '<',
+                (_, 1) => 'v',
+                _ => '^',
+            };
+            grid[step[1]] = arrow;
+        }
+        write!(f, "{}", grid)?;
+        write!(f, "{} blocks moved", self.path.len() - 1)
     }
 }
 
@@ -68,103 +127,68 @@ fn allowed_directions_ultra_crucible(
 }
 
 fn shortest_path(
-    grid: &Grid,
-    start: (Direction, Coordinate),
+    grid: &Grid<usize>,
+    starts: &[(Direction, Coordinate)],
     goal: Coordinate,
-    get_allowed_directions: fn(usize, Direction) -> Vec<Direction>,
-) -> Option<usize> {
-    let mut heap = BinaryHeap::new();
-    let mut distances = HashMap::new();
-
-    // Different than regular dijkstra - we need to consider "slower" states if we haven't
-    // visited them yet.
-    let mut visited = HashSet::new();
-
-    distances.insert(start.1, 0);
-    heap.push(State::new(start.1, start.0, 0, 0));
-
-    while let Some(State {
-        coordinate,
-        direction: previous_direction,
-        distance,
-        num_straight,
-    }) = heap.pop()
-    {
-        if coordinate == goal {
-            return Some(distance);
-        }
-
-        let visited_key = (coordinate, previous_direction, num_straight);
-
-        if visited.contains(&visited_key)
-            && distance > *distances.get(&coordinate).unwrap_or(&usize::MAX)
-        {
-            continue;
-        }
-
-        visited.insert(visited_key);
-
-        for direction in get_allowed_directions(num_straight, previous_direction) {
-            let next = match coordinate.navigate(
-                &direction,
-                (grid.cols() - 1) as i64,
-                (grid.rows() - 1) as i64,
-            ) {
-                Some(next) => next,
-                None => continue,
-            };
-
-            let next_distance = distance
-                + grid
-                    .get(next.x, next.y)
-                    .to_string()
-                    .parse::<usize>()
-                    .unwrap();
-
-            if next_distance < *distances.get(&next).unwrap_or(&usize::MAX) {
-                distances.insert(next, next_distance);
-            }
-            heap.push(State::new(
-                next,
-                direction,
-                next_distance,
-                if direction == previous_direction {
+    crucible: &Crucible,
+) -> Option<Witnessed<usize, CruciblePath>> {
+    let (max_x, max_y) = (grid.cols() as i64 - 1, grid.rows() as i64 - 1);
+    let successors = |&(coordinate, previous, num_straight): &State| {
+        (crucible.allowed_directions)(num_straight, previous)
+            .into_iter()
+            .filter_map(move |direction| {
+                let next = coordinate.navigate(&direction, max_x, max_y)?;
+                let num_straight = if direction == previous {
                     num_straight + 1
                 } else {
                     1
-                },
-            ));
-        }
-    }
-
-    None
+                };
+                Some(((next, direction, num_straight), grid[next]))
+            })
+    };
+    // Every block loses at least one heat, so the distance left never overestimates
+    let heuristic = |&(coordinate, _, _): &State| {
+        (goal.x - coordinate.x).unsigned_abs() as usize
+            + (goal.y - coordinate.y).unsigned_abs() as usize
+    };
+    let found = astar_multi(
+        starts
+            .iter()
+            .map(|(direction, start)| (*start, *direction, 0)),
+        successors,
+        heuristic,
+        |&(coordinate, _, num_straight)| {
+            coordinate == goal && num_straight >= crucible.min_straight
+        },
+    )?;
+
+    let path = CruciblePath {
+        grid: grid.clone(),
+        path: found
+            .path
+            .iter()
+            .map(|(coordinate, _, _)| *coordinate)
+            .collect(),
+        min_straight: crucible.min_straight,
+        max_straight: crucible.max_straight,
+    };
+    Some(Witnessed::new(found.cost, path))
 }
 
 fn main() {
-    let grid = Grid::from_input();
-    let cost = shortest_path(
-        &grid,
-        (Direction::Down, Coordinate::new(0, 0)),
-        Coordinate::new(grid.cols() as i64 - 1, grid.rows() as i64 - 1),
-        allowed_directions_crucible,
-    )
-    .unwrap();
-    println!("Part 1: {}", cost);
-
-    let cost = shortest_path(
-        &grid,
-        (Direction::Down, Coordinate::new(0, 0)),
-        Coordinate::new(grid.cols() as i64 - 1, grid.rows() as i64 - 1),
-        allowed_directions_ultra_crucible,
-    )
-    .unwrap();
-
-    let cost_right = shortest_path(
-        &grid,
-        (Direction::Right, Coordinate::new(0, 0)),
-        Coordinate::new(grid.cols() as i64 - 1, grid.rows() as i64 - 1),
-        allowed_directions_ultra_crucible,
-    )
-    .unwrap();
-    println!("Part 2: {}", aoc2023::min(cost, cost_right));
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_17);
+    let grid = Grid::parse_input(|c| c.to_digit(10).unwrap() as usize);
+    let start = Coordinate::new(0, 0);
+    let goal = Coordinate::new(grid.cols() as i64 - 1, grid.rows() as i64 - 1);
+    let cost = shortest_path(&grid, &[(Direction::Down, start)], goal, &CRUCIBLE).unwrap();
+    println!("Part 1: {}", cost.answer);
+    cost.report("Part 1");
+    cost.check("Part 1");
+
+    // The ultra crucible can't turn before it has moved, so it has to try starting both ways
+    let starts = [(Direction::Down, start), (Direction::Right, start)];
+    let cost = shortest_path(&grid, &starts, goal, &ULTRA_CRUCIBLE).unwrap();
+    println!("Part 2: {}", cost.answer);
+    cost.report("Part 2");
+    cost.check("Part 2");
 }
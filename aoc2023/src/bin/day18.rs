@@ -64,6 +64,7 @@ fn solve(directions: Vec<(char, i64)>) -> i64 {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_18);
     println!("Part 1: {}", solve(parse_coordinates_part1()));
     println!("Part 2: {}", solve(parse_coordinates_part2()));
 }
@@ -245,6 +245,7 @@ fn search_range(workflows: &HashMap<String, Workflow>) -> Vec<ObjectRange> {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_19);
     let content = read_input_content();
 
     let (workflows, objects) = content.split_once("\n\n").unwrap();
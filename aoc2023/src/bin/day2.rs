@@ -110,6 +110,7 @@ fn part1() {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_2);
     part1();
 
     part2();
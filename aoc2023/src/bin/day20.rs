@@ -1,6 +1,6 @@
 use std::collections::{HashMap, VecDeque};
 
-use aoc2023::read_input_lines;
+use aoc2023::{read_input_lines, trace};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 enum Pulse {
@@ -78,10 +78,14 @@ impl Graph {
         let mut queue = VecDeque::from(vec![(0, Pulse::Low, self.name_to_index["button"])]);
 
         while let Some((origin, pulse, index)) = queue.pop_front() {
-            if pulse == Pulse::High && index == 15 {
-                println!("Sent high to {} from {}", index, origin);
-                println!("Last sent: {:?}", self.last_sent[origin]);
-            }
+            trace!(
+                "2023::day20",
+                "{} -{}-> {} (last sent by origin: {:?})",
+                origin,
+                pulse,
+                index,
+                self.last_sent[origin]
+            );
             let (output_pulse, outputs) = self.send(index, pulse);
             match output_pulse {
                 Pulse::High => high_pulses += outputs.len(),
@@ -194,5 +198,6 @@ fn part1() {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_20);
     part1();
 }
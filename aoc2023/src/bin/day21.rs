@@ -1,41 +1,52 @@
-use std::collections::HashSet;
+use aoc2023::search::Reachable;
+use aoc2023::{Coordinate, Grid};
 
-use aoc2023::{Coordinate, Direction, Grid};
+/// A plot can be ended on if it's close enough, and reached an even or odd number of steps like
+/// the total: the remaining steps can be spent going back and forth
+fn can_end_on(distance: usize, steps: usize) -> bool {
+    distance <= steps && distance % 2 == steps % 2
+}
 
-fn find_next_coordinates(grid: &Grid, current: &HashSet<Coordinate>) -> HashSet<Coordinate> {
-    let mut result = HashSet::new();
-    for c in current {
-        for direction in &[
-            Direction::Up,
-            Direction::Down,
-            Direction::Left,
-            Direction::Right,
-        ] {
-            if let Some(next) =
-                c.navigate(direction, grid.cols() as i64 - 1, grid.rows() as i64 - 1)
-            {
-                if grid.get(next.x, next.y) == '.' {
-                    result.insert(next);
-                }
-            }
-        }
-    }
+fn part1(grid: &Grid, start: Coordinate) -> usize {
+    let iterations = 64;
+    let steps = grid.bfs(start, |tile| *tile != '#', |_, _| true);
+    steps
+        .distances()
+        .row_slices()
+        .flatten()
+        .flatten()
+        .filter(|&&distance| can_end_on(distance, iterations))
+        .count()
+}
 
-    result
+fn plots_within(reachable: &Reachable<Coordinate, usize>, steps: usize) -> usize {
+    reachable
+        .iter()
+        .filter(|(_, distance)| can_end_on(*distance, steps))
+        .count()
 }
 
-fn main() {
-    let mut grid = Grid::from_input();
-    let (x, y) = grid.find('S').unwrap();
-    grid.update(x as i64, y as i64, '.');
-    let iterations = 64;
+/// The start is in the middle of a row and a column without rocks, and the steps run out right
+/// at the edge of a copy of the map: 65 steps to the edge of the first one, then 131 for every
+/// copy after it. Counted at the edge of the first few copies the plots grow quadratically with
+/// the number of copies, so three counts are enough to extrapolate to the rest.
+fn part2(grid: &Grid, start: Coordinate) -> usize {
+    let total_steps = 26501365;
+    let size = grid.width();
+    let to_edge = total_steps % size;
+    let tiled = grid.tiled();
+    let reachable = tiled.bfs(start, to_edge + 2 * size, |tile| *tile != '#', |_, _| true);
 
+    let [a, b, c] = [0, 1, 2].map(|copies| plots_within(&reachable, to_edge + copies * size));
+    let n = total_steps / size;
+    a + n * (b - a) + n * (n - 1) / 2 * (c + a - 2 * b)
+}
 
-    let mut current = HashSet::new();
-    current.insert(Coordinate::new(x as i64, y as i64));
-    for _ in 0..iterations {
-        current = find_next_coordinates(&grid, &current);
-    }
+fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_21);
+    let grid = Grid::from_input();
+    let start = grid.find('S').unwrap();
 
-    println!("Part 1: {}", current.len());
+    println!("Part 1: {}", part1(&grid, start));
+    println!("Part 2: {}", part2(&grid, start));
 }
@@ -1,13 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use aoc2023::read_input_lines;
-
-#[derive(Debug)]
-struct Grid {
-    width: usize,
-    height: usize,
-    data: Vec<Vec<char>>,
-}
+use aoc2023::{Coordinate, Grid};
 
 #[derive(Debug)]
 struct Number {
@@ -23,84 +16,50 @@ struct GearCandidate {
     number: usize,
 }
 
-impl Grid {
-    fn parse(input: &[String]) -> Self {
-        let width = input[0].len();
-        let height = input.len();
-        let data = input.iter().map(|line| line.chars().collect()).collect();
-        Grid {
-            width,
-            height,
-            data,
-        }
-    }
-
-    fn find_adjacent(&self, x: usize, y: usize, detector: fn(char) -> bool) -> Vec<(usize, usize)> {
-        let mut result = Vec::new();
-        let x_start = if x == 0 { 0 } else { x - 1 };
-        let y_start = if y == 0 { 0 } else { y - 1 };
-        for xn in x_start..=x + 1 {
-            for yn in y_start..=y + 1 {
-                if (xn == x && yn == y) || (xn >= self.width || yn >= self.height) {
-                    continue;
-                }
+fn has_symbol_adjacent(grid: &Grid, x: usize, y: usize) -> bool {
+    grid.neighbors8_where(Coordinate::new(x as i64, y as i64), |c| {
+        !c.is_numeric() && *c != '.'
+    })
+    .next()
+    .is_some()
+}
 
-                if detector(self.data[yn][xn]) {
-                    result.push((xn, yn));
-                }
-            }
+fn find_numbers(grid: &Grid) -> Vec<Number> {
+    let mut result = Vec::new();
+    let mut current_number = String::new();
+
+    let add_number = |current_number: &mut String, x: usize, y: usize, result: &mut Vec<Number>| {
+        if !current_number.is_empty() {
+            result.push(Number {
+                number: current_number.parse().unwrap(),
+                x_range: (x - current_number.len(), x - 1),
+                y,
+            });
         }
 
-        result
-    }
+        current_number.clear();
+    };
 
-    fn has_symbol_adjacent(&self, x: usize, y: usize) -> bool {
-        !self
-            .find_adjacent(x, y, |c| !c.is_numeric() && c != '.')
-            .is_empty()
-    }
-
-    fn find_numbers(&self) -> Vec<Number> {
-        let mut result = Vec::new();
-        let mut current_number = String::new();
-        let mut found_symbol = false;
-
-        let add_number =
-            |current_number: &mut String, x: usize, y: usize, result: &mut Vec<Number>| {
-                if !current_number.is_empty() {
-                    result.push(Number {
-                        number: current_number.parse().unwrap(),
-                        x_range: (x - current_number.len(), x - 1),
-                        y,
-                    });
-                }
-
-                current_number.clear();
-            };
-
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if !self.data[y][x].is_numeric() {
-                    add_number(&mut current_number, x, y, &mut result);
-                } else {
-                    found_symbol = found_symbol || self.has_symbol_adjacent(x, y);
-                    current_number += &self.data[y][x].to_string();
-                }
+    for (y, row) in grid.row_slices().enumerate() {
+        for (x, c) in row.iter().enumerate() {
+            if !c.is_numeric() {
+                add_number(&mut current_number, x, y, &mut result);
+            } else {
+                current_number.push(*c);
             }
-            add_number(&mut current_number, self.width - 1, y, &mut result);
         }
-        result
+        add_number(&mut current_number, grid.width() - 1, y, &mut result);
     }
+    result
 }
 
 fn part1() {
-    let grid = Grid::parse(&read_input_lines());
-    let number = grid
-        .find_numbers()
+    let grid = Grid::from_input();
+    let number = find_numbers(&grid)
         .into_iter()
         .filter(|number| {
             for x in number.x_range.0..=number.x_range.1 {
-                if grid.has_symbol_adjacent(x, number.y) {
+                if has_symbol_adjacent(&grid, x, number.y) {
                     return true;
                 }
             }
@@ -113,18 +72,18 @@ fn part1() {
 }
 
 fn part2() {
-    let grid = Grid::parse(&read_input_lines());
+    let grid = Grid::from_input();
 
-    let res = grid
-        .find_numbers()
+    let res = find_numbers(&grid)
         .into_iter()
         .flat_map(|number| {
             let mut result = HashSet::new();
             for x in number.x_range.0..=number.x_range.1 {
-                for candidate in grid.find_adjacent(x, number.y, |c| c == '*') {
+                let at = Coordinate::new(x as i64, number.y as i64);
+                for (candidate, _) in grid.neighbors8_where(at, |c| *c == '*') {
                     result.insert(GearCandidate {
-                        x: candidate.0,
-                        y: candidate.1,
+                        x: candidate.x as usize,
+                        y: candidate.y as usize,
                         number: number.number,
                     });
                 }
@@ -146,6 +105,7 @@ fn part2() {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_3);
     part1();
 
     part2();
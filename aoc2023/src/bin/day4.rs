@@ -72,6 +72,7 @@ fn part2() {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_4);
     part1();
 
     part2();
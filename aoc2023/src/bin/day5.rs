@@ -1,7 +1,9 @@
-use aoc2023::read_input_content;
+use aoc2023::fuzz::{self, Generator, Rng};
+use aoc2023::solvers::Solvers;
+use aoc2023::{arith, cli, read_input_content};
 use std::collections::VecDeque;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Range {
     start: u64,
     end: u64,
@@ -15,7 +17,11 @@ impl Range {
     fn from_length(start: u64, len: u64) -> Self {
         Self {
             start,
-            end: start + len - 1,
+            end: arith::sub(
+                arith::add(start, len, "Range::from_length"),
+                1,
+                "Range::from_length",
+            ),
         }
     }
 
@@ -40,7 +46,7 @@ impl Range {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct RangeMapping {
     source: Range,
     destination: Range,
@@ -62,21 +68,25 @@ impl RangeMapping {
     fn map(&self, value: u64) -> Option<u64> {
         if self.source.contains(value) {
             let offset = value - self.source.start;
-            Some(self.destination.start + offset)
+            Some(arith::add(
+                self.destination.start,
+                offset,
+                "RangeMapping::map",
+            ))
         } else {
             None
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Map {
     mappings: VecDeque<RangeMapping>,
 }
 
 impl Map {
     fn new(mut mappings: Vec<RangeMapping>) -> Self {
-        mappings.sort_by(|a, b| a.source.start.cmp(&b.source.start));
+        mappings.sort_by_key(|m| m.source.start);
         Self {
             mappings: VecDeque::from(mappings),
         }
@@ -112,7 +122,7 @@ impl Map {
                     let length = intersection.end - intersection.start + 1;
 
                     result.push_back(Range::from_length(
-                        mapping.destination.start + offset,
+                        arith::add(mapping.destination.start, offset, "Map::merge_intervals"),
                         length,
                     ));
                 }
@@ -123,7 +133,7 @@ impl Map {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Almanac {
     seeds: Vec<u64>,
     maps: Vec<Map>,
@@ -185,28 +195,152 @@ fn part1() {
     println!("Part 1: {}", result);
 }
 
-fn part22() {
-    let alma = Almanac::parse(read_input_content());
+fn lowest_location_of_ranges(alma: &Almanac) -> u64 {
     let mut intervals = alma
         .seeds
         .chunks_exact(2)
         .map(|chunk| Range::from_length(chunk[0], chunk[1]))
         .collect::<Vec<_>>();
 
-    intervals.sort_by(|a, b| a.start.cmp(&b.start));
-    let result = alma
-        .maps
+    intervals.sort_by_key(|i| i.start);
+    alma.maps
         .iter()
         .fold(VecDeque::from(intervals), |a, b| b.merge_intervals(a))
         .iter()
         .map(|i| i.start)
         .min()
-        .unwrap();
+        .unwrap()
+}
 
-    println!("Part 2: {:?}", result);
+/// Looks up every single seed in the ranges, only feasible for small inputs
+fn lowest_location_brute_force(alma: &Almanac) -> u64 {
+    alma.seeds
+        .chunks_exact(2)
+        .flat_map(|chunk| chunk[0]..chunk[0] + chunk[1])
+        .map(|seed| alma.get_location(seed))
+        .min()
+        .unwrap()
+}
+
+fn part22() {
+    let alma = Almanac::parse(read_input_content());
+    let solvers = Solvers::new("Part 2", "intervals", lowest_location_of_ranges)
+        .add("brute-force", lowest_location_brute_force);
+    println!("Part 2: {:?}", solvers.run(&alma));
+}
+
+const MAP_NAMES: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+impl std::fmt::Display for Almanac {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let seeds = self.seeds.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        writeln!(f, "seeds: {}", seeds.join(" "))?;
+        for (map, name) in self.maps.iter().zip(MAP_NAMES.iter().cycle()) {
+            writeln!(f, "\n{} map:", name)?;
+            for mapping in map.mappings.iter() {
+                writeln!(
+                    f,
+                    "{} {} {}",
+                    mapping.destination.start,
+                    mapping.source.start,
+                    mapping.source.end - mapping.source.start + 1
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct AlmanacGenerator;
+
+impl Generator for AlmanacGenerator {
+    type Input = Almanac;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Almanac {
+        let size = size as i64;
+        let seeds = (0..rng.between(1, 3))
+            .flat_map(|_| [rng.between(0, size * 10), rng.between(1, size)])
+            .map(|n| n as u64)
+            .collect();
+
+        let maps = (0..rng.between(1, MAP_NAMES.len() as i64))
+            .map(|_| {
+                // The source ranges of a map never overlap
+                let mut cursor = 0;
+                let mappings = (0..rng.between(1, size / 2 + 1))
+                    .map(|_| {
+                        let source = cursor + rng.between(0, size);
+                        let length = rng.between(1, size);
+                        cursor = source + length;
+                        let destination = rng.between(0, size * 10);
+                        RangeMapping {
+                            source: Range::from_length(source as u64, length as u64),
+                            destination: Range::from_length(destination as u64, length as u64),
+                        }
+                    })
+                    .collect();
+                Map::new(mappings)
+            })
+            .collect();
+
+        Almanac { seeds, maps }
+    }
+
+    fn shrink(&self, alma: &Almanac) -> Vec<Almanac> {
+        let mut candidates = Vec::new();
+        let pairs = alma.seeds.len() / 2;
+        for i in 0..pairs {
+            if pairs > 1 {
+                let mut smaller = alma.clone();
+                smaller.seeds.drain(i * 2..i * 2 + 2);
+                candidates.push(smaller);
+            }
+
+            if alma.seeds[i * 2 + 1] > 1 {
+                let mut smaller = alma.clone();
+                smaller.seeds[i * 2 + 1] /= 2;
+                candidates.push(smaller);
+            }
+        }
+
+        for i in 0..alma.maps.len() {
+            if alma.maps.len() > 1 {
+                let mut smaller = alma.clone();
+                smaller.maps.remove(i);
+                candidates.push(smaller);
+            }
+
+            for j in 0..alma.maps[i].mappings.len() {
+                let mut smaller = alma.clone();
+                smaller.maps[i].mappings.remove(j);
+                candidates.push(smaller);
+            }
+        }
+
+        candidates
+    }
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_5);
+    if cli::args().flag("fuzz") {
+        fuzz::run(
+            &AlmanacGenerator,
+            |alma| lowest_location_of_ranges(&Almanac::parse(alma.to_string())),
+            lowest_location_brute_force,
+        );
+        return;
+    }
+
     part1();
     part22();
 }
@@ -1,6 +1,13 @@
-use aoc2023::{read_input_lines, solve_quadratic};
+//! Answers: Result
+//!
+//! Part 2 is the same computation on the kerning-corrected input (day6_2.txt), so there's a
+//! single result for whichever input is given.
 
-#[derive(Debug)]
+use aoc2023::fuzz::{self, Generator, Rng};
+use aoc2023::solvers::Solvers;
+use aoc2023::{cli, read_input_lines, solve_quadratic};
+
+#[derive(Debug, Clone)]
 struct Race {
     time: u64,
     distance: u64,
@@ -12,7 +19,7 @@ impl Race {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Paper {
     races: Vec<Race>,
 }
@@ -41,18 +48,108 @@ impl Paper {
     }
 }
 
+impl std::fmt::Display for Paper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Time:    ")?;
+        for race in self.races.iter() {
+            write!(f, " {:>5}", race.time)?;
+        }
+        write!(f, "\nDistance:")?;
+        for race in self.races.iter() {
+            write!(f, " {:>5}", race.distance)?;
+        }
+        writeln!(f)
+    }
+}
+
 fn number_of_ways(time: f64, distance: f64) -> u64 {
     let (lower, upper) = solve_quadratic(1.0, -time, distance);
-    (upper - 1.0).ceil() as u64 - lower.floor() as u64
+    // If the record is the best possible distance both roots are the same, and there's no way to
+    // beat it
+    ((upper - 1.0).ceil() - lower.floor()).max(0.0) as u64
 }
 
-fn main() {
-    let paper = Paper::parse(read_input_lines());
-    let res = paper
+fn ways_to_win(paper: &Paper) -> u64 {
+    paper
         .races
         .iter()
         .map(|r| number_of_ways(r.time as f64, r.distance as f64))
-        .product::<u64>();
+        .product::<u64>()
+}
+
+/// Tries every possible time to hold the button
+fn ways_to_win_brute_force(paper: &Paper) -> u64 {
+    paper
+        .races
+        .iter()
+        .map(|r| {
+            (0..=r.time)
+                .filter(|hold| hold * (r.time - hold) > r.distance)
+                .count() as u64
+        })
+        .product::<u64>()
+}
+
+struct RaceGenerator;
 
-    println!("Result: {}", res);
+impl Generator for RaceGenerator {
+    type Input = Paper;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Paper {
+        let races = (0..rng.between(1, 4))
+            .map(|_| {
+                let time = rng.between(1, size as i64 * 10) as u64;
+                // The record is a distance someone actually managed to go
+                let hold = rng.between(0, time as i64) as u64;
+                Race::new(time, hold * (time - hold))
+            })
+            .collect();
+        Paper { races }
+    }
+
+    fn shrink(&self, paper: &Paper) -> Vec<Paper> {
+        let mut candidates = Vec::new();
+        for i in 0..paper.races.len() {
+            if paper.races.len() > 1 {
+                let mut smaller = paper.clone();
+                smaller.races.remove(i);
+                candidates.push(smaller);
+            }
+
+            let race = &paper.races[i];
+            for (time, distance) in [
+                (race.time / 2, race.distance / 4),
+                (race.time - 1, race.distance),
+                (race.time, race.distance / 2),
+            ] {
+                if time > 0 && (time, distance) != (race.time, race.distance) {
+                    let mut smaller = paper.clone();
+                    smaller.races[i] = Race::new(time, distance);
+                    candidates.push(smaller);
+                }
+            }
+        }
+
+        candidates
+    }
+}
+
+fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_6);
+    if cli::args().flag("fuzz") {
+        fuzz::run(
+            &RaceGenerator,
+            |paper| {
+                let lines = paper.to_string().lines().map(String::from).collect();
+                ways_to_win(&Paper::parse(lines))
+            },
+            ways_to_win_brute_force,
+        );
+        return;
+    }
+
+    let paper = Paper::parse(read_input_lines());
+    let solvers = Solvers::new("Result", "quadratic", ways_to_win)
+        .add("brute-force", ways_to_win_brute_force);
+    println!("Result: {}", solvers.run(&paper));
 }
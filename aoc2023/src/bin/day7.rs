@@ -135,6 +135,7 @@ fn find_score(with_joker: bool) -> usize {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_7);
     println!("Part 1: {}", find_score(false));
     println!("Part 2: {}", find_score(true));
 }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use aoc2023::parallel::par_map_reduce;
 use aoc2023::{lcm, read_input_lines};
 
 #[derive(Debug)]
@@ -60,17 +61,17 @@ pub fn part1() {
 
 pub fn part2() {
     let document = Document::parse(read_input_lines());
-    let moves = document
+    let ghosts = document
         .nodes
         .keys()
         .filter(|k| k.ends_with('A'))
-        .map(|k| document.moves_to_navigate(k))
-        .reduce(lcm)
-        .unwrap();
+        .collect::<Vec<_>>();
+    let moves = par_map_reduce(&ghosts, |k| document.moves_to_navigate(k), lcm).unwrap();
     println!("{:?}", moves);
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_8);
     part1();
     part2();
 }
@@ -53,6 +53,7 @@ fn part2() {
 }
 
 fn main() {
+    aoc2023::inputs::embed(aoc2023::inputs::embedded::DAY_2023_9);
     part1();
     part2();
 }
@@ -0,0 +1,82 @@
+//! Minimal command line handling shared by all the binaries.
+//!
+//! Positional arguments are kept in order (the first one is usually the input file), options are
+//! given as `--name` or `--name=value` and can be mixed in anywhere. The options that always take
+//! a value are declared by the modules that read them, and can be given as `--name value` as well.
+//! An option with an optional value, like `--play[=FPS]`, only takes one with `=`.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// The options that always take a value, so the argument after them is theirs
+const VALUE_OPTIONS: &[&[&str]] = &[
+    crate::dashboard::VALUE_OPTIONS,
+    crate::fuzz::VALUE_OPTIONS,
+    crate::parallel::VALUE_OPTIONS,
+    crate::results::VALUE_OPTIONS,
+    crate::simulation::VALUE_OPTIONS,
+    crate::solvers::VALUE_OPTIONS,
+];
+
+#[derive(Debug, Default)]
+pub struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, Option<String>>,
+}
+
+impl Args {
+    /// `value_options` take the argument after them as their value, unless it's another option
+    pub fn parse<I: IntoIterator<Item = String>>(args: I, value_options: &[&str]) -> Self {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            if let Some(option) = arg.strip_prefix("--") {
+                let (name, value) = match option.split_once('=') {
+                    Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                    None if value_options.contains(&option) => (
+                        option.to_string(),
+                        args.next_if(|next| !next.starts_with("--")),
+                    ),
+                    None => (option.to_string(), None),
+                };
+                parsed.options.insert(name, value);
+            } else {
+                parsed.positional.push(arg);
+            }
+        }
+
+        parsed
+    }
+
+    pub fn positional(&self, index: usize) -> Option<&str> {
+        self.positional.get(index).map(|s| s.as_str())
+    }
+
+    /// Whether the option was given at all, with or without a value
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options.get(name).and_then(|v| v.as_deref())
+    }
+}
+
+/// The name of the running binary, like `day16`
+pub fn program() -> Option<String> {
+    std::env::args()
+        .next()
+        .as_deref()
+        .map(std::path::Path::new)
+        .and_then(|p| p.file_stem())
+        .map(|s| s.to_string_lossy().into_owned())
+}
+
+/// The arguments of the running process, parsed once.
+pub fn args() -> &'static Args {
+    static ARGS: OnceLock<Args> = OnceLock::new();
+    ARGS.get_or_init(|| {
+        let value_options = VALUE_OPTIONS.concat();
+        Args::parse(std::env::args().skip(1), &value_options)
+    })
+}
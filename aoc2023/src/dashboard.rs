@@ -0,0 +1,196 @@
+//! A dashboard served on localhost, listing the last answers, timings and status of every day
+//! along with the final frames of their simulations. Days can be re-run from the page.
+//!
+//! There's nothing to fetch from elsewhere, the page and its stylesheet are part of the binary.
+//! Requests have to be for the dashboard's own host, so other web pages open in the browser can't
+//! run days through it.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+
+use crate::days::{self, Day, Input, DAYS};
+use crate::results::Results;
+use crate::runner::{format_elapsed, Status};
+
+/// `--port`, read by the hub binary
+pub const VALUE_OPTIONS: &[&str] = &["port"];
+
+const PAGE: &str = include_str!("../assets/dashboard.html");
+const STYLE: &str = include_str!("../assets/dashboard.css");
+
+pub fn serve(port: u16, results_dir: PathBuf) -> io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    println!("Dashboard running on http://127.0.0.1:{}", port);
+
+    let mut results = Results::load(results_dir);
+    for stream in listener.incoming() {
+        if let Err(e) = stream.and_then(|stream| handle(stream, port, &mut results)) {
+            eprintln!("Request failed: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+fn handle(mut stream: TcpStream, port: u16, results: &mut Results) -> io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    // Only where the request is for and where it comes from matter, none of the requests carry
+    // anything in the body
+    let (mut host, mut origin) = (None, None);
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+        if let Some((name, value)) = header.split_once(':') {
+            let value = Some(value.trim().to_string());
+            match name.trim().to_ascii_lowercase().as_str() {
+                "host" => host = value,
+                "origin" => origin = value,
+                _ => {}
+            }
+        }
+        header.clear();
+    }
+    if !is_allowed(host.as_deref(), origin.as_deref(), port) {
+        return respond(&mut stream, "403 Forbidden", "text/plain", "Forbidden");
+    }
+
+    let mut parts = request.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    match (method, path) {
+        ("GET", "/") => respond(&mut stream, "200 OK", "text/html", &page(results)),
+        ("GET", "/style.css") => respond(&mut stream, "200 OK", "text/css", STYLE),
+        ("POST", "/run-all") => {
+            for day in DAYS {
+                results.run(day, Input::Real, &[]);
+            }
+            results.save()?;
+            redirect(&mut stream, "/")
+        }
+        ("POST", path) => match parse_run_path(path) {
+            Some(day) => {
+                results.run(day, Input::Real, &[]);
+                results.save()?;
+                redirect(&mut stream, &format!("/#{}", anchor(day)))
+            }
+            None => respond(&mut stream, "404 Not Found", "text/plain", "Not found"),
+        },
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "Not found"),
+    }
+}
+
+/// The host has to be the dashboard itself, which keeps other sites from reaching it by DNS
+/// rebinding. Browsers send an origin with form posts, which has to be the dashboard's page.
+fn is_allowed(host: Option<&str>, origin: Option<&str>, port: u16) -> bool {
+    let hosts = [format!("127.0.0.1:{}", port), format!("localhost:{}", port)];
+    let is_dashboard = |host: &str| hosts.iter().any(|h| h == host);
+    host.is_some_and(is_dashboard)
+        && origin.is_none_or(|origin| origin.strip_prefix("http://").is_some_and(is_dashboard))
+}
+
+/// `/run/2023/16`
+fn parse_run_path(path: &str) -> Option<&'static Day> {
+    let mut parts = path.strip_prefix("/run/")?.split('/');
+    let year = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    days::find(year, day)
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+fn redirect(stream: &mut TcpStream, location: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 303 See Other\r\nLocation: {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        location
+    )
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn anchor(day: &Day) -> String {
+    format!("{}-{}", day.year, day.day)
+}
+
+fn page(results: &Results) -> String {
+    let mut content = String::new();
+    for year in days::years() {
+        content.push_str(&format!(
+            "<h2>{}</h2>\n<table>\n<tr><th>Day</th><th>Answers</th><th>Time</th><th>Status</th><th></th></tr>\n",
+            year
+        ));
+        let mut frames = String::new();
+        for day in DAYS.iter().filter(|d| d.year == year) {
+            content.push_str(&row(day, results));
+            for (name, frame) in results.frames(day, Input::Real) {
+                frames.push_str(&format!(
+                    "<h3>day{} {}</h3>\n<pre>{}</pre>\n",
+                    day.day,
+                    escape(&name),
+                    escape(&frame)
+                ));
+            }
+        }
+        content.push_str("</table>\n");
+        content.push_str(&frames);
+    }
+
+    PAGE.replace("{{content}}", &content)
+}
+
+fn row(day: &Day, results: &Results) -> String {
+    let run = results.get(day, Input::Real);
+    let answers = match run {
+        Some(run) => day
+            .parts
+            .iter()
+            .zip(run.answers.iter())
+            .map(|(part, answer)| match answer.contains('\n') {
+                true => format!("{}:<pre>{}</pre>", part, escape(answer)),
+                false => format!("{}: {}<br>", part, escape(answer)),
+            })
+            .collect::<String>(),
+        None => String::new(),
+    };
+    let (time, status) = match run {
+        Some(run) => {
+            let status = match &run.status {
+                Status::Failed(reason) => {
+                    format!("<span class=\"failed\">failed: {}</span>", escape(reason))
+                }
+                other => format!("<span class=\"{0}\">{0}</span>", other.name()),
+            };
+            (format_elapsed(run.elapsed), status)
+        }
+        None => (
+            String::new(),
+            "<span class=\"missing\">not run</span>".to_string(),
+        ),
+    };
+
+    format!(
+        "<tr id=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><form method=\"post\" action=\"/run/{}/{}\"><button>Run</button></form></td></tr>\n",
+        anchor(day),
+        day.day,
+        answers,
+        time,
+        status,
+        day.year,
+        day.day
+    )
+}
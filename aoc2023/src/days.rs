@@ -0,0 +1,111 @@
+//! Every day of both years, what it solves and where its input lives.
+//!
+//! The 2023 days are binaries of their own (`day16 <input>`), the 2022 days are all run through
+//! the `aoc2022` binary (`aoc2022 10 <input>`). They are expected to be built into the same
+//! target directory as whatever binary is looking for them.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+    /// A single result computed for both parts, given different inputs
+    Combined,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "Part 1"),
+            Part::Two => write!(f, "Part 2"),
+            Part::Combined => write!(f, "Result"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Input {
+    Real,
+    Example,
+}
+
+impl Input {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Input::Real => "real",
+            Input::Example => "example",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "real" => Some(Input::Real),
+            "example" => Some(Input::Example),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    /// The answers the day prints, in order
+    pub parts: &'static [Part],
+}
+
+// Generated by build.rs from the day files, a day's `//! Answers:` line says which parts it has
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+pub fn years() -> Vec<u32> {
+    let mut years = DAYS.iter().map(|d| d.year).collect::<Vec<_>>();
+    years.dedup();
+    years
+}
+
+pub fn find(year: u32, day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day{}", self.year, self.day)
+    }
+}
+
+impl Day {
+    /// Where the input is, relative to the root of the repository
+    pub fn input_path(&self, input: Input) -> PathBuf {
+        match (self.year, input) {
+            (2022, Input::Real) => format!("inputs/2022/day{}/input.txt", self.day),
+            (2022, Input::Example) => format!("inputs/2022/day{}/example.txt", self.day),
+            (_, Input::Real) => format!("inputs/{}/day{}.txt", self.year, self.day),
+            (_, Input::Example) => format!("inputs/{}/day{}_example.txt", self.year, self.day),
+        }
+        .into()
+    }
+
+    /// The executable solving the day, next to the currently running one
+    pub fn executable(&self) -> Option<PathBuf> {
+        let dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+        let name = match self.year {
+            2022 => "aoc2022".to_string(),
+            _ => format!("day{}", self.day),
+        };
+        let path = dir.join(format!("{}{}", name, std::env::consts::EXE_SUFFIX));
+        path.exists().then_some(path)
+    }
+
+    /// The command running the day on the given input, without any extra flags
+    pub fn command(&self, input: Input) -> Option<Command> {
+        let mut command = Command::new(self.executable()?);
+        if self.year == 2022 {
+            command.arg(self.day.to_string());
+        }
+        command.arg(self.input_path(input));
+        Some(command)
+    }
+}
@@ -0,0 +1,207 @@
+//! Cross-checking clever solvers against deliberately naive ones on random inputs.
+//!
+//! A day provides a [`Generator`] for random, well-formed inputs (the `Display` implementation of
+//! the input renders the puzzle text) and two solvers. [`cross_check`] runs both on a number of
+//! random cases and, when they disagree (or one of them panics), shrinks the failing input to a
+//! minimal one that still fails.
+
+use std::fmt::{Debug, Display};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::cli;
+
+pub const VALUE_OPTIONS: &[&str] = &["size", "seed"];
+
+/// A small, seedable random number generator (splitmix64). Good enough for generating inputs, and
+/// it keeps failures reproducible from the seed alone.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `low..=high`. Panics if the range is empty.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        assert!(
+            low <= high,
+            "Rng::between({}, {}): the range is empty",
+            low,
+            high
+        );
+        let span = (high - low) as u64 + 1;
+        low + (self.next_u64() % span) as i64
+    }
+
+    /// A number in `0..n`. Panics if `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "Rng::below(0): the range is empty");
+        self.next_u64() % n
+    }
+}
+
+pub trait Generator {
+    /// The generated input, where `Display` renders it as puzzle text
+    type Input: Clone + Display;
+
+    /// A random, well-formed input. The size is a rough measure of how large it should be.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Self::Input;
+
+    /// Smaller variations of the input, tried in order when shrinking a failing case
+    fn shrink(&self, input: &Self::Input) -> Vec<Self::Input>;
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub cases: usize,
+    pub size: usize,
+    pub seed: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            cases: 1000,
+            size: 10,
+            seed: 1,
+        }
+    }
+}
+
+impl Config {
+    /// `--fuzz[=CASES]`, `--size=N` and `--seed=N`
+    pub fn from_args() -> Self {
+        let args = cli::args();
+        let parse = |name: &str, default: u64| {
+            args.value(name)
+                .map(|v| v.parse::<u64>().expect("Invalid number"))
+                .unwrap_or(default)
+        };
+        let default = Self::default();
+        let config = Self {
+            cases: parse("fuzz", default.cases as u64) as usize,
+            size: parse("size", default.size as u64) as usize,
+            seed: parse("seed", default.seed),
+        };
+        if config.size == 0 {
+            eprintln!("--size has to be at least 1");
+            std::process::exit(1);
+        }
+        config
+    }
+}
+
+/// What a solver came up with, or the message it panicked with
+pub type Outcome<A> = Result<A, String>;
+
+#[derive(Debug, Clone)]
+pub struct Failure<I, A> {
+    /// The seed of the case that first failed, before shrinking
+    pub seed: u64,
+    pub input: I,
+    pub solver: Outcome<A>,
+    pub naive: Outcome<A>,
+}
+
+fn outcome<I, A, F: Fn(&I) -> A>(f: &F, input: &I) -> Outcome<A> {
+    // Panics are expected while hunting for failures, keep them from flooding the output. Only
+    // while the solver runs, a panic anywhere else is a bug that should be reported.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| f(input)));
+    panic::set_hook(hook);
+
+    result.map_err(|e| {
+        e.downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| e.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".into())
+    })
+}
+
+/// Compare the solver with the naive solver on random inputs. Returns the first failure, shrunk
+/// as far as possible, or None if they agreed on every case.
+pub fn cross_check<G, A, F, N>(
+    generator: &G,
+    config: &Config,
+    solver: F,
+    naive: N,
+) -> Option<Failure<G::Input, A>>
+where
+    G: Generator,
+    A: PartialEq,
+    F: Fn(&G::Input) -> A,
+    N: Fn(&G::Input) -> A,
+{
+    let check = |input: &G::Input| {
+        let (solver, naive) = (outcome(&solver, input), outcome(&naive, input));
+        match (&solver, &naive) {
+            (Ok(a), Ok(b)) if a == b => None,
+            _ => Some((solver, naive)),
+        }
+    };
+
+    let mut failure = None;
+    for case in 0..config.cases as u64 {
+        let seed = config.seed.wrapping_add(case);
+        let input = generator.generate(&mut Rng::new(seed), config.size);
+        if let Some((solver, naive)) = check(&input) {
+            failure = Some(Failure {
+                seed,
+                input,
+                solver,
+                naive,
+            });
+            break;
+        }
+    }
+
+    // Greedily take the first smaller input that still fails, until none does
+    if let Some(failure) = failure.as_mut() {
+        while let Some((input, (solver, naive))) = generator
+            .shrink(&failure.input)
+            .into_iter()
+            .find_map(|candidate| check(&candidate).map(|outcomes| (candidate, outcomes)))
+        {
+            failure.input = input;
+            failure.solver = solver;
+            failure.naive = naive;
+        }
+    }
+
+    failure
+}
+
+/// Run the cross-check with the configuration from the command line and report the result,
+/// exiting with a failure status if the solvers disagree.
+pub fn run<G, A, F, N>(generator: &G, solver: F, naive: N)
+where
+    G: Generator,
+    A: PartialEq + Debug,
+    F: Fn(&G::Input) -> A,
+    N: Fn(&G::Input) -> A,
+{
+    let config = Config::from_args();
+    match cross_check(generator, &config, solver, naive) {
+        None => println!(
+            "All {} cases agree (size {}, seed {})",
+            config.cases, config.size, config.seed
+        ),
+        Some(failure) => {
+            println!("Mismatch, first seen with seed {}", failure.seed);
+            println!("Minimal input:\n{}", failure.input);
+            println!("Solver: {:?}", failure.solver);
+            println!("Naive:  {:?}", failure.naive);
+            std::process::exit(1);
+        }
+    }
+}
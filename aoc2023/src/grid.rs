@@ -0,0 +1,540 @@
+//! A two dimensional grid of cells.
+//!
+//! Cells are addressed by `x` and `y`, never by row and column: `x` is the column, counting from
+//! the left, and `y` is the row, counting from the top, so `y` grows downwards like the input
+//! reads. A `Coordinate` indexes the grid directly (`grid[c]`), `get_checked` is the variant for
+//! coordinates that may be outside of it. Code that thinks in rows and columns should convert
+//! with `Coordinate::new(col, row)` at the edge rather than carrying `(row, col)` pairs around.
+//!
+//! Most puzzles are grids of characters, which is what `Grid` holds unless told otherwise. Other
+//! cell types are parsed from the text with a function per cell, like
+//! `Grid::parse(input, |c| c.to_digit(10).unwrap())` for a grid of digits.
+//!
+//! The cells are kept in one buffer, row after row, so a row is a contiguous slice.
+
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Index, IndexMut};
+
+use crate::{hash, read_input_content, search, Coordinate, Direction};
+
+/// The offsets of the eight surrounding cells, and the direction of the ones that have one
+const SURROUNDING: [((i64, i64), Option<Direction>); 8] = [
+    ((-1, -1), None),
+    ((0, -1), Some(Direction::Up)),
+    ((1, -1), None),
+    ((-1, 0), Some(Direction::Left)),
+    ((1, 0), Some(Direction::Right)),
+    ((-1, 1), None),
+    ((0, 1), Some(Direction::Down)),
+    ((1, 1), None),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T = char> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+/// A row that isn't as wide as the first one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaggedRow {
+    /// Counted from 1, so for parsed text it's the line number
+    pub row: usize,
+    pub len: usize,
+    pub width: usize,
+}
+
+impl fmt::Display for RaggedRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Row {} has {} cells, but the grid is {} wide",
+            self.row, self.len, self.width
+        )
+    }
+}
+
+impl Error for RaggedRow {}
+
+impl Grid {
+    pub fn from_input() -> Self {
+        Self::parse_input(|c| c)
+    }
+
+    pub fn from_raw(s: &str) -> Self {
+        Self::parse(s, |c| c)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parse every character of the text into a cell, one line per row. Panics if the lines
+    /// aren't all as long.
+    pub fn parse<F: FnMut(char) -> T>(s: &str, f: F) -> Self {
+        Self::try_parse(s, f).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_parse<F: FnMut(char) -> T>(s: &str, mut f: F) -> Result<Self, RaggedRow> {
+        Self::try_from_rows(
+            s.lines()
+                .map(|l| l.chars().map(&mut f).collect::<Vec<_>>())
+                .collect(),
+        )
+    }
+
+    /// Parse several grids separated by empty lines, like `parse`. A line of only whitespace is a
+    /// row, just like in `parse`. Panics if the lines of a grid aren't all as long.
+    pub fn parse_many<F: FnMut(char) -> T>(s: &str, f: F) -> Vec<Self> {
+        Self::try_parse_many(s, f).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// The row of a ragged grid is the line number in the whole text, not in the grid
+    pub fn try_parse_many<F: FnMut(char) -> T>(s: &str, mut f: F) -> Result<Vec<Self>, RaggedRow> {
+        let mut grids = Vec::new();
+        let mut rows = Vec::new();
+        let mut first_line = 0;
+        // The empty line at the end closes the last grid
+        for (i, line) in s.lines().chain([""]).enumerate() {
+            if !line.is_empty() {
+                rows.push(line.chars().map(&mut f).collect());
+                continue;
+            }
+
+            if !rows.is_empty() {
+                let grid =
+                    Self::try_from_rows(std::mem::take(&mut rows)).map_err(|e| RaggedRow {
+                        row: first_line + e.row,
+                        ..e
+                    })?;
+                grids.push(grid);
+            }
+            first_line = i + 1;
+        }
+
+        Ok(grids)
+    }
+
+    pub fn parse_input<F: FnMut(char) -> T>(f: F) -> Self {
+        Self::parse(&read_input_content(), f)
+    }
+
+    /// Panics if the rows aren't all as long
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        Self::try_from_rows(rows).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_from_rows(rows: Vec<Vec<T>>) -> Result<Self, RaggedRow> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some((row, len)) = rows
+            .iter()
+            .map(|row| row.len())
+            .enumerate()
+            .find(|(_, len)| *len != width)
+        {
+            return Err(RaggedRow {
+                row: row + 1,
+                len,
+                width,
+            });
+        }
+
+        let height = rows.len();
+        let cells = rows.into_iter().flatten().collect();
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn rows(&self) -> usize {
+        self.height
+    }
+
+    pub fn cols(&self) -> usize {
+        self.width
+    }
+
+    pub fn is_valid(&self, x: i64, y: i64) -> bool {
+        x < self.width as i64 && y < self.height as i64 && x >= 0 && y >= 0
+    }
+
+    /// Whether the coordinate is inside of the grid
+    pub fn contains(&self, c: Coordinate) -> bool {
+        self.is_valid(c.x, c.y)
+    }
+
+    pub fn get_checked(&self, c: Coordinate) -> Option<&T> {
+        self.contains(c)
+            .then(|| &self.cells[c.y as usize * self.width + c.x as usize])
+    }
+
+    pub fn get_checked_mut(&mut self, c: Coordinate) -> Option<&mut T> {
+        self.contains(c)
+            .then(|| &mut self.cells[c.y as usize * self.width + c.x as usize])
+    }
+
+    /// The neighbors straight up, down, left and right that are inside of the grid, with the
+    /// direction to get there
+    pub fn neighbors(&self, c: Coordinate) -> impl Iterator<Item = (Coordinate, Direction)> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| (c.step(&direction), direction))
+            .filter(|(n, _)| self.contains(*n))
+    }
+
+    /// Like `neighbors`, but only the ones whose cell is passable
+    pub fn neighbors_where<'a, F: Fn(&T) -> bool + 'a>(
+        &'a self,
+        c: Coordinate,
+        passable: F,
+    ) -> impl Iterator<Item = (Coordinate, Direction)> + 'a {
+        self.neighbors(c).filter(move |(n, _)| passable(&self[*n]))
+    }
+
+    /// All eight surrounding cells that are inside of the grid, diagonals included. Only the
+    /// neighbors that aren't diagonal have a direction.
+    pub fn neighbors8(
+        &self,
+        c: Coordinate,
+    ) -> impl Iterator<Item = (Coordinate, Option<Direction>)> + '_ {
+        SURROUNDING
+            .into_iter()
+            .map(move |((x, y), direction)| (Coordinate::new(c.x + x, c.y + y), direction))
+            .filter(|(n, _)| self.contains(*n))
+    }
+
+    /// Like `neighbors8`, but only the ones whose cell is passable
+    pub fn neighbors8_where<'a, F: Fn(&T) -> bool + 'a>(
+        &'a self,
+        c: Coordinate,
+        passable: F,
+    ) -> impl Iterator<Item = (Coordinate, Option<Direction>)> + 'a {
+        self.neighbors8(c).filter(move |(n, _)| passable(&self[*n]))
+    }
+
+    /// The coordinate of a cell, by its position in the buffer
+    fn coordinate(&self, index: usize) -> Coordinate {
+        Coordinate::new((index % self.width) as i64, (index / self.width) as i64)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every row, from the top
+    pub fn row_slices(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// A grid of the same size with every cell mapped
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// The fewest steps from the start to every cell, moving up, down, left and right. Only
+    /// passable cells are entered, and a step is only taken if `can_step(from, to)` allows it,
+    /// like a limit on how far up a step can climb. The start is reached whatever it holds.
+    pub fn bfs<P, S>(&self, start: Coordinate, passable: P, can_step: S) -> Distances
+    where
+        P: Fn(&T) -> bool,
+        S: Fn(&T, &T) -> bool,
+    {
+        let reachable = search::bfs_all([start], |&c| {
+            self.neighbors(c)
+                .map(|(next, _)| next)
+                .filter(|&next| passable(&self[next]) && can_step(&self[c], &self[next]))
+                .collect::<Vec<_>>()
+        });
+
+        let mut distance = self.map(|_| None);
+        let mut previous = self.map(|_| None);
+        for (c, steps) in reachable.iter() {
+            distance[*c] = Some(steps);
+            previous[*c] = reachable.previous(c).copied();
+        }
+
+        Distances { distance, previous }
+    }
+
+    /// A view of the grid repeated in every direction
+    pub fn tiled(&self) -> Tiled<'_, T> {
+        Tiled { grid: self }
+    }
+}
+
+/// The result of `Grid::bfs`: how many steps every cell is from the start, and which cell it was
+/// reached from
+#[derive(Debug, Clone)]
+pub struct Distances {
+    distance: Grid<Option<usize>>,
+    previous: Grid<Option<Coordinate>>,
+}
+
+impl Distances {
+    /// `None` if the cell can't be reached, or is outside of the grid
+    pub fn distance(&self, c: Coordinate) -> Option<usize> {
+        self.distance.get_checked(c).copied().flatten()
+    }
+
+    /// The cell the target was reached from, `None` for the start and cells that weren't reached
+    pub fn previous(&self, c: Coordinate) -> Option<Coordinate> {
+        self.previous.get_checked(c).copied().flatten()
+    }
+
+    /// The distance to every cell, `None` for the ones that can't be reached
+    pub fn distances(&self) -> &Grid<Option<usize>> {
+        &self.distance
+    }
+
+    /// The steps from the start to the target, both included
+    pub fn path_to(&self, target: Coordinate) -> Option<Vec<Coordinate>> {
+        self.distance(target)?;
+        let mut path = vec![target];
+        while let Some(previous) = self.previous(*path.last().unwrap()) {
+            path.push(previous);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// The grid repeated in every direction, for maps that never end. Every coordinate, negative ones
+/// included, is a cell in one of the copies: `tile` tells which copy, and `base` which cell of
+/// the grid it's a copy of. The grid itself is tile 0,0.
+#[derive(Debug)]
+pub struct Tiled<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<T> Clone for Tiled<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Tiled<'_, T> {}
+
+impl<'a, T> Tiled<'a, T> {
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+
+    /// The cell of the grid that the coordinate is a copy of
+    pub fn base(&self, c: Coordinate) -> Coordinate {
+        Coordinate::new(
+            c.x.rem_euclid(self.grid.width as i64),
+            c.y.rem_euclid(self.grid.height as i64),
+        )
+    }
+
+    /// Which copy of the grid the coordinate falls in, counted in whole grids from the original
+    pub fn tile(&self, c: Coordinate) -> Coordinate {
+        Coordinate::new(
+            c.x.div_euclid(self.grid.width as i64),
+            c.y.div_euclid(self.grid.height as i64),
+        )
+    }
+
+    /// The neighbors straight up, down, left and right. There's always four of them.
+    pub fn neighbors(&self, c: Coordinate) -> impl Iterator<Item = (Coordinate, Direction)> {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| (c.step(&direction), direction))
+    }
+
+    /// Like `neighbors`, but only the ones whose cell is passable
+    pub fn neighbors_where<F: Fn(&T) -> bool + 'a>(
+        self,
+        c: Coordinate,
+        passable: F,
+    ) -> impl Iterator<Item = (Coordinate, Direction)> + 'a {
+        self.neighbors(c).filter(move |(n, _)| passable(&self[*n]))
+    }
+
+    /// Like `Grid::bfs`, but across the copies. The map never ends, so only the cells at most
+    /// `max_steps` away are searched.
+    pub fn bfs<P, S>(
+        &self,
+        start: Coordinate,
+        max_steps: usize,
+        passable: P,
+        can_step: S,
+    ) -> search::Reachable<Coordinate, usize>
+    where
+        P: Fn(&T) -> bool,
+        S: Fn(&T, &T) -> bool,
+    {
+        search::bfs_within([start], max_steps, |&c| {
+            self.neighbors(c)
+                .map(|(next, _)| next)
+                .filter(|&next| passable(&self[next]) && can_step(&self[c], &self[next]))
+                .collect::<Vec<_>>()
+        })
+    }
+}
+
+impl<T> Index<Coordinate> for Tiled<'_, T> {
+    type Output = T;
+
+    fn index(&self, c: Coordinate) -> &T {
+        &self.grid[self.base(c)]
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid `width` cells wide and `height` cells high, every one of them `c`
+    pub fn from_size(width: usize, height: usize, c: T) -> Self {
+        Self {
+            cells: vec![c; width * height],
+            width,
+            height,
+        }
+    }
+
+    // Going through the row means a column past the end can't wrap around into the next row
+    pub fn get(&self, x: i64, y: i64) -> T {
+        self.row(y as usize)[x as usize].clone()
+    }
+
+    pub fn update(&mut self, x: i64, y: i64, c: T) {
+        self.row_mut(y as usize)[x as usize] = c;
+    }
+
+    pub fn insert_row(&mut self, index: usize, c: T) {
+        let at = index * self.width;
+        self.cells
+            .splice(at..at, std::iter::repeat_n(c, self.width));
+        self.height += 1;
+    }
+
+    pub fn insert_column(&mut self, index: usize, c: T) {
+        let rows = self
+            .row_slices()
+            .map(|row| {
+                let mut row = row.to_vec();
+                row.insert(index, c.clone());
+                row
+            })
+            .collect();
+        *self = Self::from_rows(rows);
+    }
+
+    /// A grid of the given size, with every cell taken from this grid
+    fn remapped<F: Fn(usize, usize) -> (usize, usize)>(
+        &self,
+        width: usize,
+        height: usize,
+        from: F,
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (x, y) = from(x, y);
+                self.cells[y * self.width + x].clone()
+            })
+            .collect();
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.remapped(self.height, self.width, |x, y| (y, x))
+    }
+
+    pub fn rotate_left(&self) -> Self {
+        self.remapped(self.height, self.width, |x, y| (self.width - 1 - y, x))
+    }
+
+    pub fn rotate_right(&self) -> Self {
+        self.remapped(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
+}
+
+impl<T: Hash> Grid<T> {
+    /// A 64 bit hash of the size and every cell, for caches of states that are too big to keep
+    /// around as keys. Different grids can share a fingerprint, however unlikely.
+    pub fn fingerprint(&self) -> u64 {
+        hash::fingerprint(self)
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// One bit per cell, row after row, set where the cell holds the value. A compact key for a
+    /// state where only some cells change, like the rocks that roll while the walls stay put.
+    pub fn bitmask(&self, value: &T) -> Vec<u64> {
+        let mut mask = vec![0; self.cells.len().div_ceil(64)];
+        for (i, cell) in self.cells.iter().enumerate() {
+            if cell == value {
+                mask[i / 64] |= 1 << (i % 64);
+            }
+        }
+        mask
+    }
+
+    pub fn find(&self, c: T) -> Option<Coordinate> {
+        self.cells
+            .iter()
+            .position(|c2| *c2 == c)
+            .map(|i| self.coordinate(i))
+    }
+
+    pub fn find_all(&self, c: T) -> Vec<Coordinate> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, c2)| **c2 == c)
+            .map(|(i, _)| self.coordinate(i))
+            .collect()
+    }
+}
+
+impl<T> Index<Coordinate> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, c: Coordinate) -> &T {
+        self.get_checked(c)
+            .unwrap_or_else(|| panic!("{},{} is outside of the grid", c.x, c.y))
+    }
+}
+
+impl<T> IndexMut<Coordinate> for Grid<T> {
+    fn index_mut(&mut self, c: Coordinate) -> &mut T {
+        self.get_checked_mut(c)
+            .unwrap_or_else(|| panic!("{},{} is outside of the grid", c.x, c.y))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.row_slices() {
+            for c in row.iter() {
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
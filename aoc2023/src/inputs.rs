@@ -0,0 +1,284 @@
+//! Reading puzzle inputs, which may be encrypted at rest.
+//!
+//! Inputs are personal and shouldn't be redistributed, so they can be kept encrypted as
+//! `day16.txt.enc` next to where the plaintext would be. Asking for `day16.txt` falls back to the
+//! encrypted file when the plaintext isn't there, and it's decrypted on the fly.
+//!
+//! The key is 32 bytes written as 64 hex characters, taken from the `AOC_INPUT_KEY` environment
+//! variable or from the file named by `AOC_INPUT_KEY_FILE` (`.aoc-input-key` by default).
+//! An encrypted file is a short header, a random nonce and the ChaCha20-Poly1305 ciphertext.
+//!
+//! With the `embed-inputs` feature the inputs are also compiled into the binaries, each binary
+//! only carrying the ones it reads: it hands its table to `embed` when it starts, like
+//! `inputs::embed(inputs::embedded::DAY_2023_16)`. Files on disk still win, the embedded copy is
+//! only used when a file isn't there, and it's what a day reads when it's run without an input
+//! path.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+use crate::days::{self, Input};
+
+/// The embedded inputs, a table per year (`YEAR_2022`) and per day (`DAY_2023_16`), and the
+/// `NAMES` of every one of them. Empty unless built with `embed-inputs`, see build.rs.
+pub mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+}
+
+type Table = &'static [(&'static str, &'static [u8])];
+
+static EMBEDDED: OnceLock<Table> = OnceLock::new();
+
+/// Make the inputs of the table readable, for a binary that embeds its inputs. Only the first
+/// table handed over is used.
+pub fn embed(table: Table) {
+    let _ = EMBEDDED.set(table);
+}
+
+const EXTENSION: &str = "enc";
+const HEADER: &[u8] = b"aoc-input-v1\n";
+const NONCE_LEN: usize = 12;
+const DEFAULT_KEY_FILE: &str = ".aoc-input-key";
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", EXTENSION));
+    name.into()
+}
+
+fn is_encrypted(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == EXTENSION)
+}
+
+/// Whether the input is there, either as plaintext, encrypted or embedded. An embedded input
+/// counts even if it's only in the binary of its day.
+pub fn exists<P: AsRef<Path>>(path: P) -> bool {
+    let path = path.as_ref();
+    let name = embedded_name(path);
+    let encrypted = format!("{}.{}", name, EXTENSION);
+    path.exists()
+        || encrypted_path(path).exists()
+        || embedded::NAMES.contains(&name.as_str())
+        || embedded::NAMES.contains(&encrypted.as_str())
+}
+
+/// The input a day reads when no path is given, which is only possible with embedded inputs
+pub fn default_path(year: u32, day: u32) -> Option<PathBuf> {
+    if !cfg!(feature = "embed-inputs") {
+        return None;
+    }
+
+    days::find(year, day).map(|day| day.input_path(Input::Real))
+}
+
+/// The embedded copy of `inputs/2023/day16.txt` is called `2023/day16.txt`
+fn embedded_name(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    match path.rfind("inputs/") {
+        Some(index) => path[index + "inputs/".len()..].to_string(),
+        None => path.trim_start_matches("./").to_string(),
+    }
+}
+
+fn embedded(path: &Path) -> Option<(&'static str, &'static [u8])> {
+    let name = embedded_name(path);
+    let encrypted = format!("{}.{}", name, EXTENSION);
+    EMBEDDED
+        .get()?
+        .iter()
+        .find(|(embedded, _)| *embedded == name || *embedded == encrypted)
+        .copied()
+}
+
+/// Read an input like `std::fs::read_to_string`, decrypting it if it's only there encrypted (or
+/// if an encrypted file was asked for directly). Falls back to the embedded copy, if any.
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    let path = path.as_ref();
+    match read_from_disk(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => match embedded(path) {
+            Some((name, data)) if is_encrypted(Path::new(name)) => {
+                decrypt_bytes(Path::new(name), data)
+            }
+            Some((name, data)) => String::from_utf8(data.to_vec())
+                .map_err(|_| invalid(format!("{} isn't valid UTF-8", name))),
+            None => Err(e),
+        },
+        result => result,
+    }
+}
+
+fn read_from_disk(path: &Path) -> io::Result<String> {
+    if is_encrypted(path) {
+        return decrypt_file(path);
+    }
+
+    match std::fs::read_to_string(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound && encrypted_path(path).exists() => {
+            decrypt_file(&encrypted_path(path))
+        }
+        result => result,
+    }
+}
+
+fn key_file() -> PathBuf {
+    std::env::var("AOC_INPUT_KEY_FILE")
+        .unwrap_or_else(|_| DEFAULT_KEY_FILE.to_string())
+        .into()
+}
+
+fn parse_key(hex: &str) -> io::Result<Key> {
+    let hex = hex.trim();
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+        .filter(|bytes| bytes.len() == 32)
+        .ok_or_else(|| invalid("The input key must be 64 hex characters".into()))?;
+    Ok(*Key::from_slice(&bytes))
+}
+
+fn key() -> io::Result<Key> {
+    if let Ok(hex) = std::env::var("AOC_INPUT_KEY") {
+        return parse_key(&hex);
+    }
+
+    let file = key_file();
+    match std::fs::read_to_string(&file) {
+        Ok(hex) => parse_key(&hex),
+        Err(e) => Err(io::Error::new(
+            e.kind(),
+            format!(
+                "No input key, set AOC_INPUT_KEY or put one in {} ({})",
+                file.display(),
+                e
+            ),
+        )),
+    }
+}
+
+/// Generate a new random key and write it to the key file, refusing to overwrite an existing one
+pub fn generate_key() -> io::Result<PathBuf> {
+    let file = key_file();
+    if file.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", file.display()),
+        ));
+    }
+
+    let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+    let hex = key.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+    // Only the owner gets to read the key
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    options.open(&file)?.write_all((hex + "\n").as_bytes())?;
+    Ok(file)
+}
+
+fn cipher() -> io::Result<ChaCha20Poly1305> {
+    Ok(ChaCha20Poly1305::new(&key()?))
+}
+
+fn encrypt(cipher: &ChaCha20Poly1305, plaintext: &[u8]) -> Vec<u8> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .expect("Encryption can't fail for inputs of this size");
+    [HEADER, nonce.as_slice(), &ciphertext].concat()
+}
+
+fn decrypt(cipher: &ChaCha20Poly1305, data: &[u8]) -> Option<Vec<u8>> {
+    let data = data.strip_prefix(HEADER)?;
+    if data.len() < NONCE_LEN {
+        return None;
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    cipher.decrypt(Nonce::from_slice(nonce), ciphertext).ok()
+}
+
+fn decrypt_file(path: &Path) -> io::Result<String> {
+    decrypt_bytes(path, &std::fs::read(path)?)
+}
+
+fn decrypt_bytes(path: &Path, data: &[u8]) -> io::Result<String> {
+    let plaintext = decrypt(&cipher()?, data).ok_or_else(|| {
+        invalid(format!(
+            "Could not decrypt {}, is it the right key?",
+            path.display()
+        ))
+    })?;
+    String::from_utf8(plaintext)
+        .map_err(|_| invalid(format!("{} isn't valid UTF-8", path.display())))
+}
+
+fn files(dir: &Path, found: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files(&path, found)?;
+        } else {
+            found.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Encrypt every plaintext file under `dir`, replacing it with its `.enc` counterpart.
+/// Returns the number of files encrypted.
+pub fn encrypt_tree<P: AsRef<Path>>(dir: P) -> io::Result<usize> {
+    let cipher = cipher()?;
+    let mut found = Vec::new();
+    files(dir.as_ref(), &mut found)?;
+
+    let mut count = 0;
+    for path in found.into_iter().filter(|p| !is_encrypted(p)) {
+        let plaintext = std::fs::read(&path)?;
+        std::fs::write(encrypted_path(&path), encrypt(&cipher, &plaintext))?;
+        std::fs::remove_file(&path)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Decrypt every `.enc` file under `dir`, replacing it with the plaintext.
+/// Returns the number of files decrypted.
+pub fn decrypt_tree<P: AsRef<Path>>(dir: P) -> io::Result<usize> {
+    let cipher = cipher()?;
+    let mut found = Vec::new();
+    files(dir.as_ref(), &mut found)?;
+
+    let mut count = 0;
+    for path in found.into_iter().filter(|p| is_encrypted(p)) {
+        let data = std::fs::read(&path)?;
+        let plaintext = decrypt(&cipher, &data).ok_or_else(|| {
+            invalid(format!(
+                "Could not decrypt {}, is it the right key?",
+                path.display()
+            ))
+        })?;
+        std::fs::write(path.with_extension(""), plaintext)?;
+        std::fs::remove_file(&path)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
@@ -1,18 +1,51 @@
-use std::{collections::VecDeque, env, path::Path};
+use std::path::{Path, PathBuf};
+
+pub mod animation;
+pub mod arith;
+pub mod cli;
+pub mod dashboard;
+pub mod days;
+pub mod fuzz;
+pub mod grid;
+pub mod hash;
+pub mod inputs;
+pub mod parallel;
+pub mod results;
+pub mod runner;
+pub mod search;
+pub mod simulation;
+pub mod solvers;
+pub mod sparse;
+pub mod status;
+pub mod trace;
+pub mod tui;
+pub mod witness;
+
+pub use grid::Grid;
+pub use sparse::SparseGrid;
 
 fn read_file<P: AsRef<Path>>(path: P) -> Vec<String> {
-    let contents = std::fs::read_to_string(path).expect("Something went wrong reading the file");
+    let contents = inputs::read_to_string(path).expect("Something went wrong reading the file");
     contents.lines().map(|s| s.to_string()).collect()
 }
 
+/// The input given on the command line, or the embedded input of the running day
+fn input_path() -> PathBuf {
+    match cli::args().positional(0) {
+        Some(path) => path.into(),
+        None => cli::program()
+            .and_then(|name| name.strip_prefix("day")?.parse().ok())
+            .and_then(|day| inputs::default_path(2023, day))
+            .expect("No input file given"),
+    }
+}
+
 pub fn read_input_lines() -> Vec<String> {
-    let args: Vec<String> = env::args().collect();
-    read_file(&args[1])
+    read_file(input_path())
 }
 
 pub fn read_input_content() -> String {
-    let args: Vec<String> = env::args().collect();
-    std::fs::read_to_string(&args[1]).expect("Something went wrong reading the file")
+    inputs::read_to_string(input_path()).expect("Something went wrong reading the file")
 }
 
 pub fn solve_quadratic(a: f64, b: f64, c: f64) -> (f64, f64) {
@@ -35,162 +68,8 @@ pub fn gcd(a: u64, b: u64) -> u64 {
 }
 
 pub fn lcm(a: u64, b: u64) -> u64 {
-    a * b / gcd(a, b)
-}
-
-#[derive(Debug, Clone)]
-pub struct Grid {
-    grid: Vec<Vec<char>>,
-}
-
-impl Grid {
-    pub fn from_input() -> Self {
-        let grid = read_input_lines()
-            .iter()
-            .map(|l| l.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        Self { grid }
-    }
-
-    pub fn from_raw(s: &str) -> Self {
-        let grid = s
-            .lines()
-            .map(|l| l.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        Self { grid }
-    }
-
-    pub fn from_size(rows: usize, cols: usize, c: char) -> Self {
-        let grid = vec![vec![c; cols]; rows];
-        Self { grid }
-    }
-
-    pub fn rows(&self) -> usize {
-        self.grid.len()
-    }
-
-    pub fn cols(&self) -> usize {
-        self.grid[0].len()
-    }
-
-    pub fn is_valid(&self, x: i64, y: i64) -> bool {
-        x < self.grid[0].len() as i64 && y < self.grid.len() as i64 && x >= 0 && y >= 0
-    }
-
-    pub fn get(&self, x: i64, y: i64) -> char {
-        self.grid[y as usize][x as usize]
-    }
-
-    pub fn update(&mut self, x: i64, y: i64, c: char) {
-        self.grid[y as usize][x as usize] = c;
-    }
-
-    pub fn find(&self, c: char) -> Option<(usize, usize)> {
-        for (y, row) in self.grid.iter().enumerate() {
-            for (x, &c2) in row.iter().enumerate() {
-                if c == c2 {
-                    return Some((x, y));
-                }
-            }
-        }
-
-        None
-    }
-
-    pub fn find_all(&self, c: char) -> Vec<(usize, usize)> {
-        let mut res = vec![];
-        for (y, row) in self.grid.iter().enumerate() {
-            for (x, &c2) in row.iter().enumerate() {
-                if c == c2 {
-                    res.push((x, y));
-                }
-            }
-        }
-
-        res
-    }
-
-    pub fn insert_row(&mut self, index: usize, c: char) {
-        let v = vec![c; self.grid[0].len()];
-        self.grid.insert(index, v);
-    }
-
-    pub fn insert_column(&mut self, index: usize, c: char) {
-        for row in self.grid.iter_mut() {
-            row.insert(index, c);
-        }
-    }
-
-    pub fn bfs(&self, starting_point: (usize, usize)) -> Vec<Vec<usize>> {
-        let mut visited = vec![vec![usize::MAX; self.cols()]; self.rows()];
-        let mut queue = VecDeque::from(vec![(0, starting_point)]);
-        while !queue.is_empty() {
-            let (distance, (x, y)) = queue.pop_front().unwrap();
-            if visited[y][x] == usize::MAX {
-                visited[y][x] = distance;
-                if self.is_valid(x as i64 - 1, y as i64) {
-                    queue.push_back((distance + 1, (x - 1, y)));
-                }
-                if self.is_valid(x as i64 + 1, y as i64) {
-                    queue.push_back((distance + 1, (x + 1, y)));
-                }
-                if self.is_valid(x as i64, y as i64 - 1) {
-                    queue.push_back((distance + 1, (x, y - 1)));
-                }
-                if self.is_valid(x as i64, y as i64 + 1) {
-                    queue.push_back((distance + 1, (x, y + 1)));
-                }
-            }
-        }
-
-        visited
-    }
-
-    pub fn transpose(&self) -> Self {
-        let mut grid = vec![vec!['.'; self.rows()]; self.cols()];
-        for (y, row) in self.grid.iter().enumerate() {
-            for (x, &c) in row.iter().enumerate() {
-                grid[x][y] = c;
-            }
-        }
-
-        Self { grid }
-    }
-
-    pub fn rotate_left(&self) -> Self {
-        let mut grid = vec![vec!['.'; self.rows()]; self.cols()];
-        for (y, row) in self.grid.iter().enumerate() {
-            for (x, &c) in row.iter().enumerate() {
-                grid[self.rows() - x - 1][y] = c;
-            }
-        }
-
-        Self { grid }
-    }
-
-    pub fn rotate_right(&self) -> Self {
-        let mut grid = vec![vec!['.'; self.rows()]; self.cols()];
-        for (y, row) in self.grid.iter().enumerate() {
-            for (x, &c) in row.iter().enumerate() {
-                grid[x][self.cols() - y - 1] = c;
-            }
-        }
-
-        Self { grid }
-    }
-}
-
-impl std::fmt::Display for Grid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.grid.iter() {
-            for c in row.iter() {
-                write!(f, "{}", c)?;
-            }
-            writeln!(f)?;
-        }
-
-        Ok(())
-    }
+    // Divide first, there's no need to overflow on the intermediate product
+    arith::mul(a / gcd(a, b), b, "lcm")
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -201,6 +80,26 @@ pub enum Direction {
     Down,
 }
 
+impl Direction {
+    /// In the order neighbors are visited
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// How x and y change when taking a step
+    pub fn offset(&self) -> (i64, i64) {
+        match self {
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Coordinate {
     pub x: i64,
@@ -228,6 +127,12 @@ impl Coordinate {
         Self { x, y }
     }
 
+    /// One step in the direction, with no bounds to stop it
+    pub fn step(&self, direction: &Direction) -> Self {
+        let (x, y) = direction.offset();
+        Self::new(self.x + x, self.y + y)
+    }
+
     pub fn navigate(&self, direction: &Direction, max_x: i64, max_y: i64) -> Option<Self> {
         match (self.x, self.y, direction) {
             (0, _, Direction::Left) => None,
@@ -1,3 +1,70 @@
+use aoc2023::cli;
+use aoc2023::dashboard;
+use aoc2023::inputs;
+use aoc2023::results::Results;
+use aoc2023::status;
+use aoc2023::tui;
+
+const USAGE: &str = "Usage: aoc2023 <command> [options]
+
+Commands:
+    serve [--port=N] [--results=DIR]    Serve the dashboard on localhost
+    tui [--trace=SPEC] [--results=DIR]  Run every day in a terminal interface
+    status [--results=DIR]              Show the solved parts of every year
+    inputs keygen                       Create a key for encrypting the inputs
+    inputs encrypt|decrypt [DIR]        Encrypt or decrypt every input under DIR (inputs/)";
+
 fn main() {
-    println!("Hello, world!");
+    let args = cli::args();
+    match args.positional(0) {
+        Some("serve") => {
+            let port = args
+                .value("port")
+                .map(|p| p.parse().expect("Invalid port"))
+                .unwrap_or(8023);
+            if let Err(e) = dashboard::serve(port, Results::dir_from_args()) {
+                eprintln!("Could not serve the dashboard: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some("tui") => {
+            // The days' trace output is what ends up in the log pane
+            let trace = format!("--trace={}", args.value("trace").unwrap_or("debug"));
+            if let Err(e) = tui::run(Results::load(Results::dir_from_args()), vec![trace]) {
+                eprintln!("Terminal interface failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some("status") => print!(
+            "{}",
+            status::render(&Results::load(Results::dir_from_args()))
+        ),
+        Some("inputs") => {
+            let dir = args.positional(2).unwrap_or("inputs");
+            let result = match args.positional(1) {
+                Some("keygen") => inputs::generate_key().map(|file| {
+                    format!("Wrote a new key to {}, keep it out of git", file.display())
+                }),
+                Some("encrypt") => inputs::encrypt_tree(dir)
+                    .map(|count| format!("Encrypted {} files under {}", count, dir)),
+                Some("decrypt") => inputs::decrypt_tree(dir)
+                    .map(|count| format!("Decrypted {} files under {}", count, dir)),
+                _ => {
+                    eprintln!("{}", USAGE);
+                    std::process::exit(1);
+                }
+            };
+            match result {
+                Ok(message) => println!("{}", message),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(1);
+        }
+    }
 }
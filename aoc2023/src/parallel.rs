@@ -0,0 +1,103 @@
+//! Spreading independent pieces of work over threads, for parts that are a loop where every
+//! iteration stands on its own. The items are split into one contiguous chunk per thread and the
+//! results come back in the order of the items, so the answer is the same as the sequential loop.
+//!
+//! One thread per core is used, `--threads=N` overrides it (`--threads=1` runs sequentially).
+
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::cli;
+
+pub const VALUE_OPTIONS: &[&str] = &["threads"];
+
+pub fn threads() -> usize {
+    cli::args()
+        .value("threads")
+        .map(|n| n.parse().expect("--threads should be a number"))
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+        .max(1)
+}
+
+fn chunk_size(len: usize) -> usize {
+    len.div_ceil(threads()).max(1)
+}
+
+/// Map every item, spreading them over the threads and keeping their order
+pub fn par_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let f = &f;
+    thread::scope(|scope| {
+        let handles = items
+            .chunks(chunk_size(items.len()))
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+/// Map every item and combine the results in the order of the items, `None` if there are none
+pub fn par_map_reduce<T, R, F, G>(items: &[T], map: F, reduce: G) -> Option<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+    G: Fn(R, R) -> R,
+{
+    par_map(items, map).into_iter().reduce(reduce)
+}
+
+/// The result for the first item that gives one, like `Iterator::find_map`. Threads stop looking
+/// once an earlier item has been found by another thread.
+pub fn par_find_map<T, R, F>(items: &[T], f: F) -> Option<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Option<R> + Sync,
+{
+    par_find_map_range(0..items.len(), |i| f(&items[i]))
+}
+
+/// Like `par_find_map`, but over the numbers of a range, so long ranges don't have to be
+/// collected first. Every thread gets a contiguous part of the range.
+pub fn par_find_map_range<R, F>(range: Range<usize>, f: F) -> Option<R>
+where
+    R: Send,
+    F: Fn(usize) -> Option<R> + Sync,
+{
+    let first = AtomicUsize::new(usize::MAX);
+    let (f, first) = (&f, &first);
+    let size = chunk_size(range.len());
+    thread::scope(|scope| {
+        let handles = range
+            .clone()
+            .step_by(size)
+            .map(|chunk_start| {
+                let chunk = chunk_start..(chunk_start + size).min(range.end);
+                scope.spawn(move || {
+                    for i in chunk {
+                        if first.load(Ordering::Relaxed) < i {
+                            return None;
+                        }
+                        if let Some(result) = f(i) {
+                            first.fetch_min(i, Ordering::Relaxed);
+                            return Some(result);
+                        }
+                    }
+                    None
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .find_map(|handle| handle.join().unwrap())
+    })
+}
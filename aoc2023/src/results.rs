@@ -0,0 +1,166 @@
+//! The last run of every day, kept on disk so the dashboard and `status` can show them later.
+//!
+//! Everything lives in one directory (`results/` unless `--results=DIR` is given): the runs in
+//! `runs.tsv`, one line per day and input, and the final simulation frames of each run under
+//! `frames/`.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::cli;
+
+pub const VALUE_OPTIONS: &[&str] = &["results"];
+use crate::days::{self, Day, Input};
+use crate::runner::{Run, Status};
+
+#[derive(Debug, Default)]
+pub struct Results {
+    dir: PathBuf,
+    runs: BTreeMap<(Day, Input), Run>,
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut result = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+
+    result
+}
+
+impl Results {
+    /// The results directory from `--results=DIR`
+    pub fn dir_from_args() -> PathBuf {
+        cli::args().value("results").unwrap_or("results").into()
+    }
+
+    /// Load the recorded runs, a missing file just means nothing has been run yet
+    pub fn load<P: Into<PathBuf>>(dir: P) -> Self {
+        let dir = dir.into();
+        let mut runs = BTreeMap::new();
+        let content = std::fs::read_to_string(dir.join("runs.tsv")).unwrap_or_default();
+        for line in content.lines() {
+            let fields = line.split('\t').collect::<Vec<_>>();
+            if fields.len() < 5 {
+                continue;
+            }
+
+            let (Ok(year), Ok(day), Some(input), Ok(millis)) = (
+                fields[0].parse(),
+                fields[1].parse(),
+                Input::parse(fields[2]),
+                fields[4].parse(),
+            ) else {
+                continue;
+            };
+            let Some(day) = days::find(year, day) else {
+                continue;
+            };
+
+            let status = match fields[3] {
+                "done" => Status::Done,
+                failed => {
+                    Status::Failed(unescape(failed.strip_prefix("failed: ").unwrap_or(failed)))
+                }
+            };
+            let run = Run {
+                status,
+                answers: fields[5..].iter().map(|a| unescape(a)).collect(),
+                elapsed: Duration::from_millis(millis),
+                log: String::new(),
+            };
+            runs.insert((*day, input), run);
+        }
+
+        Self { dir, runs }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let mut content = String::new();
+        for ((day, input), run) in self.runs.iter() {
+            let status = match &run.status {
+                Status::Failed(reason) => format!("failed: {}", escape(reason)),
+                other => other.name().to_string(),
+            };
+            let mut fields = vec![
+                day.year.to_string(),
+                day.day.to_string(),
+                input.name().to_string(),
+                status,
+                run.elapsed.as_millis().to_string(),
+            ];
+            fields.extend(run.answers.iter().map(|a| escape(a)));
+            content.push_str(&fields.join("\t"));
+            content.push('\n');
+        }
+
+        std::fs::write(self.dir.join("runs.tsv"), content)
+    }
+
+    pub fn get(&self, day: &Day, input: Input) -> Option<&Run> {
+        self.runs.get(&(*day, input))
+    }
+
+    /// Record a finished run. Queued and running days aren't worth keeping.
+    pub fn insert(&mut self, day: &Day, input: Input, run: Run) {
+        if matches!(run.status, Status::Done | Status::Failed(_)) {
+            self.runs.insert((*day, input), run);
+        }
+    }
+
+    /// Where the final frames of the day's simulations are kept
+    pub fn frames_dir(&self, day: &Day, input: Input) -> PathBuf {
+        self.dir
+            .join("frames")
+            .join(format!("{}-day{}-{}", day.year, day.day, input.name()))
+    }
+
+    /// The final frames of the last run, by simulation name
+    pub fn frames(&self, day: &Day, input: Input) -> Vec<(String, String)> {
+        read_frames(&self.frames_dir(day, input))
+    }
+
+    /// Run the day and record the result
+    pub fn run(&mut self, day: &Day, input: Input, args: &[String]) -> &Run {
+        let run = crate::runner::run(day, input, &self.frames_dir(day, input), args);
+        self.insert(day, input, run);
+        &self.runs[&(*day, input)]
+    }
+}
+
+fn read_frames(dir: &Path) -> Vec<(String, String)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut frames = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let path = e.path();
+            let name = path.file_stem()?.to_string_lossy().into_owned();
+            Some((name, std::fs::read_to_string(&path).ok()?))
+        })
+        .collect::<Vec<_>>();
+    frames.sort();
+    frames
+}
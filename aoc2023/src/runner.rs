@@ -0,0 +1,170 @@
+//! Running a day as a separate process and collecting what it printed.
+
+use std::io::Read;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::days::{Day, Input};
+use crate::inputs;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Queued,
+    Running,
+    Done,
+    Failed(String),
+}
+
+impl Status {
+    pub fn name(&self) -> &str {
+        match self {
+            Status::Queued => "queued",
+            Status::Running => "running",
+            Status::Done => "done",
+            Status::Failed(_) => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub status: Status,
+    /// One per part, in the order they were printed. Multi-line answers (like a CRT screen)
+    /// keep their lines.
+    pub answers: Vec<String>,
+    pub elapsed: Duration,
+    /// Everything the day wrote to stderr, such as trace events
+    pub log: String,
+}
+
+impl Run {
+    pub fn queued() -> Self {
+        Self {
+            status: Status::Queued,
+            answers: Vec::new(),
+            elapsed: Duration::ZERO,
+            log: String::new(),
+        }
+    }
+}
+
+/// Run the day to completion. The final frame of every simulation it drives is written to
+/// `frames`, and `args` are passed on as extra flags (like `--trace`).
+pub fn run(day: &Day, input: Input, frames: &Path, args: &[String]) -> Run {
+    run_cancellable(day, input, frames, args, &AtomicBool::new(false))
+}
+
+/// Like [`run`], but the day is killed as soon as `cancel` is set
+pub fn run_cancellable(
+    day: &Day,
+    input: Input,
+    frames: &Path,
+    args: &[String],
+    cancel: &AtomicBool,
+) -> Run {
+    let mut run = Run::queued();
+    let Some(mut command) = day.command(input) else {
+        run.status = Status::Failed("No executable found, build the workspace first".into());
+        return run;
+    };
+
+    if !inputs::exists(day.input_path(input)) {
+        run.status = Status::Failed(format!("No input at {}", day.input_path(input).display()));
+        return run;
+    }
+
+    // Snapshots from an earlier run would otherwise linger
+    let _ = std::fs::remove_dir_all(frames);
+    command
+        .arg(format!("--snapshot={}", frames.display()))
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let start = Instant::now();
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            run.status = Status::Failed(e.to_string());
+            return run;
+        }
+    };
+
+    // Both pipes are drained while waiting, a day printing a lot would block otherwise
+    let (mut stdout, mut stderr) = (child.stdout.take().unwrap(), child.stderr.take().unwrap());
+    let (exit, stdout, stderr) = std::thread::scope(|s| {
+        let stdout = s.spawn(move || read_all(&mut stdout));
+        let stderr = s.spawn(move || read_all(&mut stderr));
+        let exit = loop {
+            if cancel.load(Ordering::Relaxed) {
+                let _ = child.kill();
+            }
+            match child.try_wait() {
+                Ok(Some(exit)) => break Ok(exit),
+                Ok(None) => std::thread::sleep(Duration::from_millis(10)),
+                Err(e) => break Err(e),
+            }
+        };
+        (exit, stdout.join().unwrap(), stderr.join().unwrap())
+    });
+    run.elapsed = start.elapsed();
+    run.log = stderr;
+    run.answers = parse_answers(&stdout);
+    run.status = match exit {
+        Ok(_) if cancel.load(Ordering::Relaxed) => Status::Failed("Cancelled".into()),
+        Ok(exit) if exit.success() => Status::Done,
+        Ok(exit) => {
+            let last = run.log.lines().last().unwrap_or_default();
+            Status::Failed(format!("{} {}", exit, last).trim().to_string())
+        }
+        Err(e) => Status::Failed(e.to_string()),
+    };
+
+    run
+}
+
+fn read_all<R: Read>(reader: &mut R) -> String {
+    let mut bytes = Vec::new();
+    let _ = reader.read_to_end(&mut bytes);
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Pick the answers out of what a day printed. Days print either bare values or `Label: value`,
+/// and a line ending with a colon starts a block that runs until the next labeled or empty line.
+pub fn parse_answers(stdout: &str) -> Vec<String> {
+    let mut answers: Vec<String> = Vec::new();
+    let mut in_block = false;
+    for line in stdout.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            in_block = false;
+        } else if line.ends_with(':') {
+            answers.push(String::new());
+            in_block = true;
+        } else if in_block && !line.contains(": ") {
+            let block = answers.last_mut().unwrap();
+            if !block.is_empty() {
+                block.push('\n');
+            }
+            block.push_str(line);
+        } else {
+            in_block = false;
+            let value = line.split_once(": ").map(|(_, v)| v).unwrap_or(line);
+            answers.push(value.trim().to_string());
+        }
+    }
+
+    answers
+}
+
+/// Formats a duration the way it's shown next to the answers
+pub fn format_elapsed(elapsed: Duration) -> String {
+    if elapsed.as_secs() >= 1 {
+        format!("{:.2}s", elapsed.as_secs_f64())
+    } else {
+        format!("{}ms", elapsed.as_millis())
+    }
+}
@@ -0,0 +1,348 @@
+//! Searching a state space: breadth first when every step costs the same, Dijkstra when steps
+//! have a cost, and A* when there's also a heuristic for the cost that remains.
+//!
+//! A state can be anything hashable, a coordinate or a coordinate with a direction and a count
+//! of steps taken. The search is driven by a successors function, giving the states that can be
+//! reached in one step (with the cost of the step for Dijkstra and A*). Searches towards a goal
+//! return the cost and the path that was taken, the `_all` variants explore everything that's
+//! reachable and the path to any of it can be recovered afterwards. Every search can start from
+//! several states at once.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The cheapest way to a goal, from the start to the goal (both included)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<S, C> {
+    pub cost: C,
+    pub path: Vec<S>,
+}
+
+#[derive(Debug, Clone)]
+struct Node<S, C> {
+    state: S,
+    parent: Option<usize>,
+    cost: C,
+}
+
+/// Every state a search reached, with the cheapest cost of getting there and where it came from
+#[derive(Debug, Clone)]
+pub struct Reachable<S, C> {
+    index: HashMap<S, usize>,
+    nodes: Vec<Node<S, C>>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Reachable<S, C> {
+    fn new() -> Self {
+        Self {
+            index: HashMap::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Record a state, or a cheaper way to it. Returns its index.
+    fn insert(&mut self, state: S, parent: Option<usize>, cost: C) -> usize {
+        match self.index.get(&state) {
+            Some(&i) => {
+                self.nodes[i].parent = parent;
+                self.nodes[i].cost = cost;
+                i
+            }
+            None => {
+                self.index.insert(state.clone(), self.nodes.len());
+                self.nodes.push(Node {
+                    state,
+                    parent,
+                    cost,
+                });
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn path_from(&self, mut i: usize) -> Vec<S> {
+        let mut path = vec![self.nodes[i].state.clone()];
+        while let Some(parent) = self.nodes[i].parent {
+            path.push(self.nodes[parent].state.clone());
+            i = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    fn found(&self, i: usize) -> Found<S, C> {
+        Found {
+            cost: self.nodes[i].cost,
+            path: self.path_from(i),
+        }
+    }
+
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.index.get(state).map(|&i| self.nodes[i].cost)
+    }
+
+    /// The path from one of the starts to the state, if it was reached
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.index.get(state).map(|&i| self.path_from(i))
+    }
+
+    /// The state the search came from to reach this one, `None` for the starts and the states
+    /// that weren't reached
+    pub fn previous(&self, state: &S) -> Option<&S> {
+        let i = *self.index.get(state)?;
+        self.nodes[i].parent.map(|parent| &self.nodes[parent].state)
+    }
+
+    pub fn contains(&self, state: &S) -> bool {
+        self.index.contains_key(state)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Every state reached and its cost, in the order they were reached
+    pub fn iter(&self) -> impl Iterator<Item = (&S, C)> {
+        self.nodes.iter().map(|node| (&node.state, node.cost))
+    }
+}
+
+fn bfs_until<S, I, F, G>(
+    starts: impl IntoIterator<Item = S>,
+    max_steps: usize,
+    mut successors: F,
+    mut is_goal: G,
+) -> (Reachable<S, usize>, Option<usize>)
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    let mut reachable = Reachable::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !reachable.contains(&start) {
+            queue.push_back(reachable.insert(start, None, 0));
+        }
+    }
+
+    while let Some(i) = queue.pop_front() {
+        if is_goal(&reachable.nodes[i].state) {
+            return (reachable, Some(i));
+        }
+
+        if reachable.nodes[i].cost == max_steps {
+            continue;
+        }
+
+        let cost = reachable.nodes[i].cost + 1;
+        for next in successors(&reachable.nodes[i].state) {
+            if !reachable.contains(&next) {
+                queue.push_back(reachable.insert(next, Some(i), cost));
+            }
+        }
+    }
+
+    (reachable, None)
+}
+
+/// The fewest steps from the start to a goal
+pub fn bfs<S, I, F, G>(start: S, successors: F, is_goal: G) -> Option<Found<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    bfs_multi([start], successors, is_goal)
+}
+
+/// The fewest steps from any of the starts to a goal
+pub fn bfs_multi<S, I, F, G>(
+    starts: impl IntoIterator<Item = S>,
+    successors: F,
+    is_goal: G,
+) -> Option<Found<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    let (reachable, goal) = bfs_until(starts, usize::MAX, successors, is_goal);
+    goal.map(|i| reachable.found(i))
+}
+
+/// The fewest steps from any of the starts to every state that can be reached
+pub fn bfs_all<S, I, F>(starts: impl IntoIterator<Item = S>, successors: F) -> Reachable<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+{
+    bfs_until(starts, usize::MAX, successors, |_| false).0
+}
+
+/// Like `bfs_all`, but only the states at most `max_steps` away. For state spaces that never
+/// end, like a map repeating in every direction.
+pub fn bfs_within<S, I, F>(
+    starts: impl IntoIterator<Item = S>,
+    max_steps: usize,
+    successors: F,
+) -> Reachable<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+{
+    bfs_until(starts, max_steps, successors, |_| false).0
+}
+
+/// A* from the starts, stopping at the first goal taken off the queue. The heuristic must never
+/// overestimate the remaining cost (and be consistent) for the answer to be the cheapest. With
+/// a heuristic of zero this is Dijkstra.
+fn astar_until<S, C, I, F, H, G>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: F,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> (Reachable<S, C>, Option<usize>)
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+    F: FnMut(&S) -> I,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let mut reachable = Reachable::new();
+    let mut done = Vec::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if !reachable.contains(&start) {
+            let estimate = heuristic(&start);
+            let i = reachable.insert(start, None, C::default());
+            done.push(false);
+            heap.push(Reverse((estimate, C::default(), i)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        // A cheaper way to the state was found after this one was queued
+        if done[i] || cost > reachable.nodes[i].cost {
+            continue;
+        }
+        done[i] = true;
+
+        if is_goal(&reachable.nodes[i].state) {
+            return (reachable, Some(i));
+        }
+
+        for (next, step) in successors(&reachable.nodes[i].state) {
+            let next_cost = cost + step;
+            if reachable
+                .cost(&next)
+                .is_some_and(|known| known <= next_cost)
+            {
+                continue;
+            }
+
+            let estimate = next_cost + heuristic(&next);
+            let j = reachable.insert(next, Some(i), next_cost);
+            if j == done.len() {
+                done.push(false);
+            }
+            heap.push(Reverse((estimate, next_cost, j)));
+        }
+    }
+
+    (reachable, None)
+}
+
+/// The cheapest way from the start to a goal
+pub fn dijkstra<S, C, I, F, G>(start: S, successors: F, is_goal: G) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    dijkstra_multi([start], successors, is_goal)
+}
+
+/// The cheapest way from any of the starts to a goal
+pub fn dijkstra_multi<S, C, I, F, G>(
+    starts: impl IntoIterator<Item = S>,
+    successors: F,
+    is_goal: G,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    let (reachable, goal) = astar_until(starts, successors, |_| C::default(), is_goal);
+    goal.map(|i| reachable.found(i))
+}
+
+/// The cheapest way from any of the starts to every state that can be reached
+pub fn dijkstra_all<S, C, I, F>(
+    starts: impl IntoIterator<Item = S>,
+    successors: F,
+) -> Reachable<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+    F: FnMut(&S) -> I,
+{
+    astar_until(starts, successors, |_| C::default(), |_| false).0
+}
+
+/// The cheapest way from the start to a goal, guided by a heuristic that never overestimates
+/// the cost that remains
+pub fn astar<S, C, I, F, H, G>(
+    start: S,
+    successors: F,
+    heuristic: H,
+    is_goal: G,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+    F: FnMut(&S) -> I,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    astar_multi([start], successors, heuristic, is_goal)
+}
+
+/// Like `astar`, from any of the starts
+pub fn astar_multi<S, C, I, F, H, G>(
+    starts: impl IntoIterator<Item = S>,
+    successors: F,
+    heuristic: H,
+    is_goal: G,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+    F: FnMut(&S) -> I,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let (reachable, goal) = astar_until(starts, successors, heuristic, is_goal);
+    goal.map(|i| reachable.found(i))
+}
@@ -0,0 +1,211 @@
+//! Step based simulations, and a driver that can run them headless, record every frame to a
+//! directory of text snapshots, play them back in the terminal or turn them into a GIF.
+//! A snapshot keeps just the final frame, which is what the dashboard shows.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::animation::{Animation, Palette};
+use crate::cli;
+
+pub const VALUE_OPTIONS: &[&str] = &["record", "snapshot", "gif", "scale", "every"];
+
+pub trait Simulation {
+    /// Advance the simulation a single step
+    fn step(&mut self);
+
+    fn is_done(&self) -> bool;
+
+    /// A text snapshot of the current state
+    fn render(&self) -> String;
+
+    /// The colors used for the characters of the rendering when exporting images
+    fn palette(&self) -> Palette {
+        Palette::default()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Mode {
+    Headless,
+    Record(PathBuf),
+    Snapshot(PathBuf),
+    Play(Duration),
+    Gif { path: PathBuf, scale: usize },
+}
+
+/// The value of an option that has to be a number above zero, exiting with an error otherwise
+fn positive<T: FromStr + PartialOrd + Default>(name: &str, value: &str) -> T {
+    match value.parse::<T>() {
+        Ok(n) if n > T::default() => n,
+        _ => {
+            eprintln!("--{} has to be a number above 0, not {}", name, value);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Driver {
+    mode: Mode,
+    /// Only every n:th frame is rendered (the last one always is)
+    every: usize,
+}
+
+impl Driver {
+    /// Just run the simulation, nothing is rendered
+    pub fn headless() -> Self {
+        Self {
+            mode: Mode::Headless,
+            every: 1,
+        }
+    }
+
+    /// Write every frame as a numbered text file into `dir`
+    pub fn record<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            mode: Mode::Record(dir.into()),
+            every: 1,
+        }
+    }
+
+    /// Only write the final frame, as `{name}.txt` in `dir`
+    pub fn snapshot<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            mode: Mode::Snapshot(dir.into()),
+            every: 1,
+        }
+    }
+
+    /// Play the frames in the terminal at the given number of frames per second
+    pub fn play(fps: f64) -> Self {
+        assert!(fps > 0.0, "Can't play {} frames per second", fps);
+        Self {
+            mode: Mode::Play(Duration::from_secs_f64(1.0 / fps)),
+            every: 1,
+        }
+    }
+
+    /// Write an animated GIF where every cell is `scale` pixels wide. The name of the simulation
+    /// is added to the file name, `sand.gif` becomes `sand-part1.gif`.
+    pub fn gif<P: Into<PathBuf>>(path: P, scale: usize) -> Self {
+        Self {
+            mode: Mode::Gif {
+                path: path.into(),
+                scale,
+            },
+            every: 1,
+        }
+    }
+
+    /// Only render every n:th frame, which keeps long simulations manageable
+    pub fn every(mut self, n: usize) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    /// Pick the mode from the `--record=DIR`, `--snapshot=DIR`, `--play[=FPS]` or `--gif=PATH`
+    /// (with an optional `--scale=N`) flags, headless otherwise. `--every=N` renders every n:th
+    /// frame only.
+    pub fn from_args() -> Self {
+        let args = cli::args();
+        let driver = if let Some(dir) = args.value("record") {
+            Self::record(dir)
+        } else if let Some(dir) = args.value("snapshot") {
+            Self::snapshot(dir)
+        } else if args.flag("play") {
+            let fps = args
+                .value("play")
+                .map(|fps| positive("play", fps))
+                .unwrap_or(10.0);
+            Self::play(fps)
+        } else if let Some(path) = args.value("gif") {
+            let scale = args
+                .value("scale")
+                .map(|scale| positive("scale", scale))
+                .unwrap_or(4);
+            Self::gif(path, scale)
+        } else {
+            Self::headless()
+        };
+
+        match args.value("every") {
+            Some(n) => driver.every(positive("every", n)),
+            None => driver,
+        }
+    }
+
+    /// Run the simulation until it's done, returning the number of steps taken. The name is used
+    /// to tell the frames apart when a day runs several simulations.
+    pub fn run<S: Simulation>(&self, name: &str, simulation: &mut S) -> usize {
+        let scale = match self.mode {
+            Mode::Gif { scale, .. } => scale,
+            _ => 1,
+        };
+        let mut animation = Animation::new(simulation.palette(), scale, 5);
+
+        let mut steps = 0;
+        self.frame(name, steps, simulation, &mut animation);
+        while !simulation.is_done() {
+            simulation.step();
+            steps += 1;
+            if steps % self.every == 0 || simulation.is_done() {
+                self.frame(name, steps, simulation, &mut animation);
+            }
+        }
+
+        match &self.mode {
+            Mode::Gif { path, .. } => animation
+                .save(named_path(path, name))
+                .expect("Could not write the animation"),
+            Mode::Snapshot(dir) => {
+                std::fs::create_dir_all(dir).expect("Could not create the snapshot directory");
+                let path = dir.join(format!("{}.txt", name));
+                std::fs::write(path, simulation.render()).expect("Could not write snapshot");
+            }
+            _ => {}
+        }
+
+        steps
+    }
+
+    fn frame<S: Simulation>(
+        &self,
+        name: &str,
+        index: usize,
+        simulation: &S,
+        animation: &mut Animation,
+    ) {
+        match &self.mode {
+            Mode::Headless | Mode::Snapshot(_) => {}
+            Mode::Gif { .. } => animation.push_text(&simulation.render()),
+            Mode::Record(dir) => {
+                std::fs::create_dir_all(dir).expect("Could not create the frame directory");
+                let path = dir.join(format!("{}-{:06}.txt", name, index));
+                std::fs::write(path, simulation.render()).expect("Could not write frame");
+            }
+            Mode::Play(delay) => {
+                let mut stdout = std::io::stdout().lock();
+                // Clear the screen and move the cursor to the top left
+                write!(
+                    stdout,
+                    "\x1b[2J\x1b[H{}\n{} step {}\n",
+                    simulation.render(),
+                    name,
+                    index
+                )
+                .and_then(|_| stdout.flush())
+                .expect("Could not write to the terminal");
+                std::thread::sleep(*delay);
+            }
+        }
+    }
+}
+
+fn named_path(path: &Path, name: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}-{}.{}", stem, name, extension))
+}
@@ -0,0 +1,89 @@
+//! Days with more than one way of solving a part register each of them by name. `--impl NAME`
+//! picks one (the first one registered is the default), and `--impl all` runs every one of them
+//! and checks that they agree, reporting their answers and timings on stderr.
+
+use std::fmt::Debug;
+use std::time::Instant;
+
+use crate::cli;
+use crate::runner::format_elapsed;
+
+pub const VALUE_OPTIONS: &[&str] = &["impl"];
+
+type Solver<'a, I, A> = Box<dyn Fn(&I) -> A + 'a>;
+
+pub struct Solvers<'a, I: ?Sized, A> {
+    part: &'static str,
+    solvers: Vec<(&'static str, Solver<'a, I, A>)>,
+}
+
+impl<'a, I: ?Sized, A: PartialEq + Debug> Solvers<'a, I, A> {
+    /// A registry starting with the default implementation, so there's always one to run
+    pub fn new<F: Fn(&I) -> A + 'a>(part: &'static str, name: &'static str, solver: F) -> Self {
+        Self {
+            part,
+            solvers: vec![(name, Box::new(solver))],
+        }
+    }
+
+    pub fn add<F: Fn(&I) -> A + 'a>(mut self, name: &'static str, solver: F) -> Self {
+        self.solvers.push((name, Box::new(solver)));
+        self
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.solvers.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// Solve with the implementation chosen on the command line
+    pub fn run(&self, input: &I) -> A {
+        match cli::args().value("impl") {
+            Some("all") => self.run_all(input),
+            Some(name) => self.run_named(name, input).unwrap_or_else(|| {
+                panic!(
+                    "No implementation of {} called {}, choose from {} or all",
+                    self.part,
+                    name,
+                    self.names().join(", ")
+                )
+            }),
+            None if cli::args().flag("impl") => panic!(
+                "--impl needs the name of an implementation of {}, choose from {} or all",
+                self.part,
+                self.names().join(", ")
+            ),
+            None => (self.solvers[0].1)(input),
+        }
+    }
+
+    pub fn run_named(&self, name: &str, input: &I) -> Option<A> {
+        self.solvers
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, solver)| solver(input))
+    }
+
+    /// Run every implementation, exiting with a failure status if they don't all agree
+    pub fn run_all(&self, input: &I) -> A {
+        let mut answers = Vec::new();
+        for (name, solver) in self.solvers.iter() {
+            let start = Instant::now();
+            let answer = solver(input);
+            eprintln!(
+                "{} {}: {:?} in {}",
+                self.part,
+                name,
+                answer,
+                format_elapsed(start.elapsed())
+            );
+            answers.push(answer);
+        }
+
+        if answers.iter().any(|a| *a != answers[0]) {
+            eprintln!("The implementations of {} disagree", self.part);
+            std::process::exit(1);
+        }
+
+        answers.swap_remove(0)
+    }
+}
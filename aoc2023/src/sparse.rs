@@ -0,0 +1,225 @@
+//! A grid without edges, for puzzles where the coordinates can go anywhere: a rope wandering
+//! around, sand piling up under a source or a tower of rocks that keeps growing.
+//!
+//! Only the cells that have been set are stored, every other cell holds the default value. The
+//! grid keeps track of the smallest box around the cells that were set, which is the region that
+//! gets rendered and converted to a dense `Grid`. The coordinates follow the same convention as
+//! `Grid`: `x` grows to the right and `y` grows downwards.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::hash::FastHashMap;
+use crate::{Coordinate, Direction, Grid};
+
+type Cells<T> = FastHashMap<Coordinate, T>;
+
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T = char> {
+    cells: Cells<T>,
+    default: T,
+    /// The top left and bottom right corners of the cells that were set
+    bounds: Option<(Coordinate, Coordinate)>,
+}
+
+impl<T: Default> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> SparseGrid<T> {
+    /// An empty grid, where every cell holds the default
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: Cells::default(),
+            default,
+            bounds: None,
+        }
+    }
+
+    pub fn get(&self, c: Coordinate) -> &T {
+        self.cells.get(&c).unwrap_or(&self.default)
+    }
+
+    /// The cell, if it has been set
+    pub fn get_checked(&self, c: Coordinate) -> Option<&T> {
+        self.cells.get(&c)
+    }
+
+    pub fn insert(&mut self, c: Coordinate, value: T) {
+        self.extend_bounds(c);
+        self.cells.insert(c, value);
+    }
+
+    /// Unsets the cell, so it holds the default again. Returns the value it was set to.
+    pub fn remove(&mut self, c: Coordinate) -> Option<T> {
+        let value = self.cells.remove(&c)?;
+        // The bounds can only shrink if the cell was on their edge
+        if let Some((min, max)) = self.bounds {
+            if c.x == min.x || c.x == max.x || c.y == min.y || c.y == max.y {
+                self.bounds = None;
+                let cells = self.cells.keys().copied().collect::<Vec<_>>();
+                for c in cells {
+                    self.extend_bounds(c);
+                }
+            }
+        }
+        Some(value)
+    }
+
+    fn extend_bounds(&mut self, c: Coordinate) {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Coordinate::new(min.x.min(c.x), min.y.min(c.y)),
+                Coordinate::new(max.x.max(c.x), max.y.max(c.y)),
+            ),
+            None => (c, c),
+        });
+    }
+
+    /// Whether the cell has been set
+    pub fn contains(&self, c: Coordinate) -> bool {
+        self.cells.contains_key(&c)
+    }
+
+    /// How many cells have been set
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The top left and bottom right corners of the cells that were set, both included
+    pub fn bounds(&self) -> Option<(Coordinate, Coordinate)> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.x - min.x + 1) as usize)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.y - min.y + 1) as usize)
+    }
+
+    /// Every cell that has been set, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.cells.iter().map(|(c, value)| (*c, value))
+    }
+
+    /// The neighbors straight up, down, left and right. There's always four of them.
+    pub fn neighbors(&self, c: Coordinate) -> impl Iterator<Item = (Coordinate, Direction)> {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| (c.step(&direction), direction))
+    }
+
+    /// Like `neighbors`, but only the ones whose cell is passable
+    pub fn neighbors_where<'a, F: Fn(&T) -> bool + 'a>(
+        &'a self,
+        c: Coordinate,
+        passable: F,
+    ) -> impl Iterator<Item = (Coordinate, Direction)> + 'a {
+        self.neighbors(c)
+            .filter(move |(n, _)| passable(self.get(*n)))
+    }
+
+    /// A grid with the same cells set, every one of them mapped
+    pub fn map<U, F: FnMut(&T) -> U>(&self, default: U, mut f: F) -> SparseGrid<U> {
+        SparseGrid {
+            cells: self.cells.iter().map(|(c, value)| (*c, f(value))).collect(),
+            default,
+            bounds: self.bounds,
+        }
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// The cells from the top left to the bottom right corner (both included) as a dense grid
+    pub fn region(&self, min: Coordinate, max: Coordinate) -> Grid<T> {
+        Grid::from_rows(
+            (min.y..=max.y)
+                .map(|y| {
+                    (min.x..=max.x)
+                        .map(|x| self.get(Coordinate::new(x, y)).clone())
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// The region of the cells that were set as a dense grid. The grid's `0,0` is the top left
+    /// corner of `bounds`.
+    pub fn to_grid(&self) -> Grid<T> {
+        match self.bounds {
+            Some((min, max)) => self.region(min, max),
+            None => Grid::from_rows(Vec::new()),
+        }
+    }
+}
+
+impl<T: PartialEq> SparseGrid<T> {
+    /// A cell that has been set to the value
+    pub fn find(&self, value: T) -> Option<Coordinate> {
+        self.iter().find(|(_, v)| **v == value).map(|(c, _)| c)
+    }
+
+    pub fn find_all(&self, value: T) -> Vec<Coordinate> {
+        self.iter()
+            .filter(|(_, v)| **v == value)
+            .map(|(c, _)| c)
+            .collect()
+    }
+}
+
+/// Every cell of the dense grid is set, at the same coordinate
+impl<T: Clone + Default> From<&Grid<T>> for SparseGrid<T> {
+    fn from(grid: &Grid<T>) -> Self {
+        let mut sparse = SparseGrid::default();
+        for (y, row) in grid.row_slices().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                sparse.insert(Coordinate::new(x as i64, y as i64), value.clone());
+            }
+        }
+        sparse
+    }
+}
+
+impl<T> Index<Coordinate> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, c: Coordinate) -> &T {
+        self.get(c)
+    }
+}
+
+/// Sets the cell to the default first if it hasn't been set, so writing through it works like
+/// `insert`
+impl<T: Clone> IndexMut<Coordinate> for SparseGrid<T> {
+    fn index_mut(&mut self, c: Coordinate) -> &mut T {
+        self.extend_bounds(c);
+        self.cells.entry(c).or_insert_with(|| self.default.clone())
+    }
+}
+
+/// Renders the region of the cells that were set, with the default for the ones in between
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                write!(f, "{}", self.get(Coordinate::new(x, y)))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
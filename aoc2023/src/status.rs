@@ -0,0 +1,142 @@
+//! A calendar of both years, showing which parts are solved, which are implemented but haven't
+//! been run, and which are missing altogether.
+
+use crate::days::{self, Day, Input, Part, DAYS};
+use crate::results::Results;
+use crate::runner::Status;
+
+const CALENDAR_DAYS: u32 = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Star {
+    /// The last run on the real input gave an answer
+    Solved,
+    /// The last run on the real input failed
+    Failed,
+    /// Implemented, but there's no answer recorded
+    Unsolved,
+    /// Not implemented
+    Missing,
+    /// Answered by the single result of part 1, there's nothing to run on its own
+    Combined,
+}
+
+impl Star {
+    fn symbol(&self) -> char {
+        match self {
+            Star::Solved => '*',
+            Star::Failed => '!',
+            Star::Unsolved => 'o',
+            Star::Missing => '.',
+            Star::Combined => '=',
+        }
+    }
+}
+
+/// Where a part goes in the two slots a day has. A combined result takes the first one.
+fn slot(part: Part) -> usize {
+    match part {
+        Part::One | Part::Combined => 0,
+        Part::Two => 1,
+    }
+}
+
+fn stars(day: Option<&Day>, results: &Results) -> [Star; 2] {
+    let mut stars = [Star::Missing; 2];
+    let Some(day) = day else {
+        return stars;
+    };
+
+    let run = results.get(day, Input::Real);
+    for (index, part) in day.parts.iter().enumerate() {
+        stars[slot(*part)] = match run {
+            Some(run) if run.status == Status::Done && run.answers.len() > index => Star::Solved,
+            Some(run) if matches!(run.status, Status::Failed(_)) => Star::Failed,
+            _ => Star::Unsolved,
+        };
+        if *part == Part::Combined {
+            stars[1] = Star::Combined;
+        }
+    }
+
+    stars
+}
+
+pub fn render(results: &Results) -> String {
+    let mut output = String::new();
+    for year in days::years() {
+        let calendar = (1..=CALENDAR_DAYS)
+            .map(|d| (d, stars(days::find(year, d), results)))
+            .collect::<Vec<_>>();
+        let count = |star| {
+            calendar
+                .iter()
+                .flat_map(|(_, stars)| stars.iter())
+                .filter(|s| **s == star)
+                .count()
+        };
+        let total = CALENDAR_DAYS as usize * 2;
+        output.push_str(&format!(
+            "{}    {}/{} stars, {} parts implemented\n",
+            year,
+            count(Star::Solved),
+            total,
+            total - count(Star::Missing)
+        ));
+
+        for week in calendar.chunks(5) {
+            let line = week
+                .iter()
+                .map(|(d, [first, second])| {
+                    format!("{:>3} {}{}", d, first.symbol(), second.symbol())
+                })
+                .collect::<Vec<_>>()
+                .join("  ");
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        let missing = DAYS
+            .iter()
+            .filter(|d| d.year == year)
+            .flat_map(|day| {
+                [Part::One, Part::Two]
+                    .into_iter()
+                    .filter(|part| stars(Some(day), results)[slot(*part)] == Star::Missing)
+                    .map(move |part| format!("day{} {}", day.day, part.to_string().to_lowercase()))
+            })
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            output.push_str(&format!("Missing: {}\n", missing.join(", ")));
+        }
+        let combined = DAYS
+            .iter()
+            .filter(|d| d.year == year && d.parts.contains(&Part::Combined))
+            .map(|day| format!("day{}", day.day))
+            .collect::<Vec<_>>();
+        if !combined.is_empty() {
+            output.push_str(&format!(
+                "One result for both parts: {}\n",
+                combined.join(", ")
+            ));
+        }
+        let last = DAYS.iter().filter(|d| d.year == year).map(|d| d.day).max();
+        if let Some(last) = last.filter(|last| *last < CALENDAR_DAYS) {
+            output.push_str(&format!(
+                "Not started: day{} to day{}\n",
+                last + 1,
+                CALENDAR_DAYS
+            ));
+        }
+        if DAYS
+            .iter()
+            .any(|d| d.year == year && d.executable().is_none())
+        {
+            output.push_str("Some days aren't built, build the workspace to run them\n");
+        }
+        output.push('\n');
+    }
+
+    output.push_str("* solved  ! failed  o not run  . missing  = same result as part 1\n");
+    output
+}
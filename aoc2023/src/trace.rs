@@ -0,0 +1,152 @@
+//! Lightweight leveled tracing to stderr, off by default.
+//!
+//! Every event has a target, which is the year and day it comes from (e.g. `2023::day20`). What
+//! gets printed is controlled by a filter given either with the `AOC_TRACE` environment variable
+//! or the `--trace` flag (the flag wins). A filter is a comma separated list of `target=level`
+//! directives and an optional bare `level` used for everything else:
+//!
+//! ```text
+//! AOC_TRACE=debug                  # debug and above from every day
+//! --trace=2023::day20=trace        # everything from 2023 day 20, nothing else
+//! --trace=2022=info,2022::day17=debug
+//! ```
+//!
+//! A directive matches a target if it is equal to it or a `::` separated prefix of it, and the
+//! most specific matching directive decides. A bare `--trace` enables `debug` for everything.
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::cli;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Some(Level::Error),
+            "warn" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Filter {
+    default: Option<Level>,
+    directives: Vec<(String, Level)>,
+}
+
+impl Filter {
+    pub fn parse(spec: &str) -> Self {
+        let mut filter = Self::default();
+        for directive in spec.split(',').map(|d| d.trim()).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => match Level::parse(level) {
+                    Some(level) => filter.directives.push((target.to_string(), level)),
+                    None => eprintln!("Ignoring invalid trace level: {}", level),
+                },
+                None => match Level::parse(directive) {
+                    Some(level) => filter.default = Some(level),
+                    None => eprintln!("Ignoring invalid trace directive: {}", directive),
+                },
+            }
+        }
+
+        filter
+    }
+
+    pub fn enabled(&self, target: &str, level: Level) -> bool {
+        let matching = self
+            .directives
+            .iter()
+            .filter(|(prefix, _)| {
+                target == prefix
+                    || target
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, level)| *level);
+
+        matching
+            .or(self.default)
+            .is_some_and(|max_level| level <= max_level)
+    }
+}
+
+fn filter() -> &'static Filter {
+    static FILTER: OnceLock<Filter> = OnceLock::new();
+    FILTER.get_or_init(|| {
+        let args = cli::args();
+        if args.flag("trace") {
+            Filter::parse(args.value("trace").unwrap_or("debug"))
+        } else {
+            Filter::parse(&std::env::var("AOC_TRACE").unwrap_or_default())
+        }
+    })
+}
+
+pub fn enabled(target: &str, level: Level) -> bool {
+    filter().enabled(target, level)
+}
+
+pub fn emit(target: &str, level: Level, args: fmt::Arguments) {
+    eprintln!("[{} {}] {}", level, target, args);
+}
+
+/// Emit a trace event if the filter allows it: `event!("2023::day20", Level::Debug, "...", ...)`.
+/// The message is only formatted when the event is enabled.
+#[macro_export]
+macro_rules! event {
+    ($target: expr, $level: expr, $($arg: tt)+) => {{
+        let level = $level;
+        if $crate::trace::enabled($target, level) {
+            $crate::trace::emit($target, level, format_args!($($arg)+));
+        }
+    }};
+}
+
+#[macro_export]
+macro_rules! info {
+    ($target: expr, $($arg: tt)+) => {
+        $crate::event!($target, $crate::trace::Level::Info, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($target: expr, $($arg: tt)+) => {
+        $crate::event!($target, $crate::trace::Level::Debug, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($target: expr, $($arg: tt)+) => {
+        $crate::event!($target, $crate::trace::Level::Trace, $($arg)+)
+    };
+}
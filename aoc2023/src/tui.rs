@@ -0,0 +1,266 @@
+//! A full-screen terminal interface for running every day: a table of days with their live
+//! status, answers and timings, and a scrollable pane with the log of the selected day.
+//!
+//! Days run one at a time on a worker thread, so the interface stays responsive while a slow day
+//! is running. Finished runs are recorded in the results directory like any other run.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::days::{Day, Input, DAYS};
+use crate::results::Results;
+use crate::runner::{self, format_elapsed, Run, Status};
+
+struct Job {
+    index: usize,
+    input: Input,
+    frames: PathBuf,
+}
+
+struct Update {
+    index: usize,
+    input: Input,
+    run: Run,
+}
+
+struct App {
+    results: Results,
+    /// The latest state of every day and input, including queued and running ones
+    runs: HashMap<(usize, Input), Run>,
+    input: Input,
+    table: TableState,
+    log_scroll: u16,
+    jobs: Sender<Job>,
+    updates: Receiver<Update>,
+}
+
+/// Run the interface until the user quits. Every day is queued right away, `args` are passed on
+/// to the days (like `--trace=debug`, which is where the log comes from).
+pub fn run(results: Results, args: Vec<String>) -> io::Result<()> {
+    let (jobs, worker_jobs) = mpsc::channel::<Job>();
+    let (worker_updates, updates) = mpsc::channel();
+    let quit = Arc::new(AtomicBool::new(false));
+    let cancel = quit.clone();
+    let worker = thread::spawn(move || {
+        for job in worker_jobs {
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            let day = &DAYS[job.index];
+            let mut running = Run::queued();
+            running.status = Status::Running;
+            let send = |run| {
+                worker_updates.send(Update {
+                    index: job.index,
+                    input: job.input,
+                    run,
+                })
+            };
+            if send(running).is_err() {
+                break;
+            }
+            let run = runner::run_cancellable(day, job.input, &job.frames, &args, &cancel);
+            if send(run).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut app = App {
+        results,
+        runs: HashMap::new(),
+        input: Input::Real,
+        table: TableState::default().with_selected(0),
+        log_scroll: 0,
+        jobs,
+        updates,
+    };
+    for index in 0..DAYS.len() {
+        app.queue(index);
+    }
+
+    let mut terminal = ratatui::init();
+    let result = app.event_loop(&mut terminal);
+    ratatui::restore();
+
+    // Don't leave a slow day running in the background
+    quit.store(true, Ordering::Relaxed);
+    drop(app);
+    let _ = worker.join();
+    result
+}
+
+impl App {
+    fn queue(&mut self, index: usize) {
+        let frames = self.results.frames_dir(&DAYS[index], self.input);
+        let job = Job {
+            index,
+            input: self.input,
+            frames,
+        };
+        if self.jobs.send(job).is_ok() {
+            self.runs.insert((index, self.input), Run::queued());
+        }
+    }
+
+    fn selected(&self) -> usize {
+        self.table.selected().unwrap_or(0)
+    }
+
+    /// What to show for the day, the latest run from this session or the recorded one
+    fn run(&self, index: usize) -> Option<&Run> {
+        self.runs
+            .get(&(index, self.input))
+            .or_else(|| self.results.get(&DAYS[index], self.input))
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            while let Ok(update) = self.updates.try_recv() {
+                let day = &DAYS[update.index];
+                if matches!(update.run.status, Status::Done | Status::Failed(_)) {
+                    self.results.insert(day, update.input, update.run.clone());
+                    self.results.save()?;
+                }
+                self.runs.insert((update.index, update.input), update.run);
+            }
+
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(Duration::from_millis(100))? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => self.select(self.selected().saturating_sub(1)),
+                KeyCode::Down | KeyCode::Char('j') => self.select(self.selected() + 1),
+                KeyCode::PageUp => self.log_scroll = self.log_scroll.saturating_sub(10),
+                KeyCode::PageDown => self.log_scroll = self.log_scroll.saturating_add(10),
+                KeyCode::Char('r') => self.queue(self.selected()),
+                KeyCode::Char('a') => (0..DAYS.len()).for_each(|index| self.queue(index)),
+                KeyCode::Char('e') => {
+                    self.input = match self.input {
+                        Input::Real => Input::Example,
+                        Input::Example => Input::Real,
+                    };
+                    self.log_scroll = 0;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        self.table.select(Some(index.min(DAYS.len() - 1)));
+        self.log_scroll = 0;
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [table_area, log_area, help_area] = Layout::vertical([
+            Constraint::Min(8),
+            Constraint::Percentage(35),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let rows = DAYS
+            .iter()
+            .enumerate()
+            .map(|(index, day)| self.row(day, self.run(index)))
+            .collect::<Vec<_>>();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(12),
+                Constraint::Length(9),
+                Constraint::Fill(1),
+                Constraint::Length(9),
+            ],
+        )
+        .header(
+            Row::new(["Day", "Status", "Answers", "Time"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .block(Block::bordered().title(format!(" Days ({} input) ", self.input.name())));
+        frame.render_stateful_widget(table, table_area, &mut self.table);
+
+        let day = &DAYS[self.selected()];
+        let log = match self.run(self.selected()) {
+            Some(Run {
+                status: Status::Failed(reason),
+                log,
+                ..
+            }) => format!("{}\n{}", log, reason),
+            Some(run) => run.log.clone(),
+            None => String::new(),
+        };
+        let log = Paragraph::new(log)
+            .scroll((self.log_scroll, 0))
+            .block(Block::bordered().title(format!(" Log: {} ", day)));
+        frame.render_widget(log, log_area);
+
+        let help = Line::from(
+            " ↑/↓ select  r re-run  a re-run all  e example/real input  PgUp/PgDn scroll log  q quit",
+        )
+        .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(help, help_area);
+    }
+
+    fn row(&self, day: &Day, run: Option<&Run>) -> Row<'static> {
+        let Some(run) = run else {
+            return Row::new([
+                Cell::from(day.to_string()),
+                Cell::from("not run").style(Style::default().fg(Color::DarkGray)),
+            ]);
+        };
+
+        let color = match run.status {
+            Status::Queued => Color::DarkGray,
+            Status::Running => Color::Yellow,
+            Status::Done => Color::Green,
+            Status::Failed(_) => Color::Red,
+        };
+        let answers = day
+            .parts
+            .iter()
+            .zip(run.answers.iter())
+            .map(|(part, answer)| match answer.lines().count() {
+                0 | 1 => format!("{}: {}", part, answer),
+                lines => format!("{}: ({} lines)", part, lines),
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        let time = match run.status {
+            Status::Done | Status::Failed(_) => format_elapsed(run.elapsed),
+            _ => String::new(),
+        };
+
+        Row::new([
+            Cell::from(day.to_string()),
+            Cell::from(run.status.name().to_string()).style(Style::default().fg(color)),
+            Cell::from(answers),
+            Cell::from(time),
+        ])
+    }
+}
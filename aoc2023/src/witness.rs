@@ -0,0 +1,53 @@
+//! Answers that come with a witness, the evidence of why the answer is what it is: the path that
+//! was taken, where a pattern was split or the order things were done in. The witness is shown
+//! with `--explain`.
+//!
+//! Witnesses that implement `Check` can also be checked without trusting the solver, by
+//! replaying them against the input. `--check` checks every witness a day produces and exits
+//! with a failure status if one doesn't hold up.
+
+use std::fmt::Display;
+
+use crate::cli;
+
+/// A witness that can be checked on its own, independently of how it was found
+pub trait Check<A> {
+    /// Whether the witness really leads to `answer`, and if not, what's wrong with it
+    fn check(&self, answer: &A) -> Result<(), String>;
+}
+
+#[derive(Debug, Clone)]
+pub struct Witnessed<A, W> {
+    pub answer: A,
+    pub witness: W,
+}
+
+impl<A, W: Display> Witnessed<A, W> {
+    pub fn new(answer: A, witness: W) -> Self {
+        Self { answer, witness }
+    }
+
+    /// Show the witness of the part if `--explain` was given
+    pub fn report(&self, part: &str) {
+        if cli::args().flag("explain") {
+            println!("{} witness:\n{}", part, self.witness);
+        }
+    }
+}
+
+impl<A, W: Display + Check<A>> Witnessed<A, W> {
+    /// Check the witness of the part if `--check` was given
+    pub fn check(&self, part: &str) {
+        if !cli::args().flag("check") {
+            return;
+        }
+
+        match self.witness.check(&self.answer) {
+            Ok(()) => eprintln!("{} witness checks out", part),
+            Err(reason) => {
+                eprintln!("{} witness doesn't check out: {}", part, reason);
+                std::process::exit(1);
+            }
+        }
+    }
+}